@@ -0,0 +1,231 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Lowercase numerals for the year within a docosade (0–21).
+const NUMERALS: [&str; 22] = [
+    "0", "i", "ii", "iii", "iv",
+    "v", "vi", "vii", "viii", "ix",
+    "x", "xi", "xii", "xiii", "xiv",
+    "xv", "xvi", "xvii", "xviii", "xix",
+    "xx", "xxi"
+];
+
+const ROMAN: [(i32, &str); 13] = [
+    (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"),
+    (100, "C"), (90, "XC"), (50, "L"), (40, "XL"),
+    (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")
+];
+
+/// Largest docosade that can still be written in Roman numerals.
+const MAX_CYCLE: i32 = 3999;
+
+/// A year of the Thelemic era, counted from the Equinox of the Gods (1904).
+///
+/// Years of the era are numbered from 0 and grouped into docosades of 22
+/// years, written as an uppercase cycle numeral followed by a lowercase year
+/// numeral ("Vxi" is year 11 of cycle 5). Years before 1904 are counted
+/// backwards the same way, the year just before the era being "0i" ante
+/// æram legis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Anno {
+    years: i32,
+}
+
+impl Anno {
+    /// Creates an Anno from the signed number of years since the Equinox of
+    /// the Gods; negative values lie before the era.
+    pub fn new(years: i32) -> Result<Self, Box<dyn std::error::Error>> {
+        if years.unsigned_abs() / 22 > MAX_CYCLE as u32 {
+            return Err(format!("Anno {} is outside the representable range", years).into());
+        }
+        Ok(Anno { years })
+    }
+
+    /// Builds an Anno from its docosade and year within it.
+    pub fn from_cycles(cycle_i: i32, cycle_ii: i32, before_era: bool) -> Result<Self, Box<dyn std::error::Error>> {
+        if cycle_i < 0 || !(0..22).contains(&cycle_ii) {
+            return Err(format!("Invalid Anno cycles {} and {}", cycle_i, cycle_ii).into());
+        }
+        let magnitude = cycle_i.checked_mul(22)
+            .and_then(|years| years.checked_add(cycle_ii))
+            .ok_or("Anno is outside the representable range")?;
+        Self::new(if before_era { -magnitude } else { magnitude })
+    }
+
+    /// Signed number of years since the Equinox of the Gods.
+    pub fn years(&self) -> i32 {
+        self.years
+    }
+
+    /// Whether the year falls before the Equinox of the Gods.
+    pub fn is_before_era(&self) -> bool {
+        self.years < 0
+    }
+
+    /// The era abbreviated: "e.n." (era nova), or "a.e.n." before it.
+    pub fn era_abbreviation(&self) -> &'static str {
+        if self.is_before_era() { "a.e.n." } else { "e.n." }
+    }
+
+    /// Docosade count, counted backwards for years before the era.
+    pub fn cycle_i(&self) -> i32 {
+        self.years.abs() / 22
+    }
+
+    /// Year within the docosade (0–21).
+    pub fn cycle_ii(&self) -> i32 {
+        self.years.abs() % 22
+    }
+
+    /// Gregorian year in which this Anno begins at the vernal equinox.
+    pub fn gregorian_year(&self) -> i32 {
+        self.years + 1904
+    }
+
+    /// The Anno in its usual numeral form, e.g. "Vxi".
+    pub fn numeral(&self) -> String {
+        format!("{}{}", roman(self.cycle_i()), NUMERALS[self.cycle_ii() as usize])
+    }
+}
+
+impl fmt::Display for Anno {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.numeral())
+    }
+}
+
+impl Serialize for Anno {
+    /// Writes the numeral alongside its parts, so consumers need not parse it.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Anno", 5)?;
+        state.serialize_field("numeral", &self.numeral())?;
+        state.serialize_field("years", &self.years)?;
+        state.serialize_field("cycle_i", &self.cycle_i())?;
+        state.serialize_field("cycle_ii", &self.cycle_ii())?;
+        state.serialize_field("before_era", &self.is_before_era())?;
+        state.end()
+    }
+}
+
+/// Writes a docosade count in uppercase Roman numerals, with 0 written as "0".
+fn roman(mut n: i32) -> String {
+    if n == 0 {
+        return "0".to_string();
+    }
+
+    let mut out = String::new();
+    for (value, numeral) in ROMAN {
+        while n >= value {
+            out.push_str(numeral);
+            n -= value;
+        }
+    }
+    out
+}
+
+impl FromStr for Anno {
+    type Err = Box<dyn std::error::Error>;
+
+    /// Parses the numeral form written by `Display`, e.g. "Vxi", "IV0" or "0i".
+    /// The cycle and year may also be separated, as in "V xi" or "V.xi", and
+    /// single-case numerals such as "vxi" are accepted when they can only be
+    /// read one way.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace() && *c != '.').collect();
+        let invalid = || format!("Invalid Anno numeral: {}", s);
+        // Numerals are ASCII; anything else cannot be sliced at byte offsets
+        if !s.is_ascii() {
+            return Err(invalid().into());
+        }
+
+        // The cycle is the uppercase (or "0") prefix, the year the remainder
+        let split = if s.starts_with('0') {
+            1
+        } else {
+            s.len() - s.trim_start_matches(|c: char| c.is_ascii_uppercase()).len()
+        };
+        if let Some(anno) = parse_cycles(&s[..split], &s[split..]) {
+            return anno;
+        }
+
+        // Without a case change to go by, try every split point
+        let upper = s.to_ascii_uppercase();
+        let lower = s.to_ascii_lowercase();
+        let mut readings = (1..s.len())
+            .filter_map(|split| parse_cycles(&upper[..split], &lower[split..]));
+        match (readings.next(), readings.next()) {
+            (Some(anno), None) => anno,
+            (Some(_), Some(_)) => Err(format!("Ambiguous Anno numeral: {}", s).into()),
+            _ => Err(invalid().into()),
+        }
+    }
+}
+
+/// Reads a cycle numeral and a year numeral, or `None` if either is malformed.
+fn parse_cycles(cycle: &str, year: &str) -> Option<Result<Anno, Box<dyn std::error::Error>>> {
+    let cycle_i = if cycle == "0" { 0 } else { parse_roman(cycle)? };
+    let cycle_ii = NUMERALS.iter().position(|numeral| *numeral == year)? as i32;
+    Some(Anno::from_cycles(cycle_i, cycle_ii, false))
+}
+
+/// Reads uppercase Roman numerals, accepting only their canonical spelling.
+fn parse_roman(s: &str) -> Option<i32> {
+    let mut rest = s;
+    let mut n = 0;
+    for (value, numeral) in ROMAN {
+        while let Some(tail) = rest.strip_prefix(numeral) {
+            n += value;
+            rest = tail;
+        }
+    }
+    (n > 0 && rest.is_empty() && roman(n) == s).then_some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_numerals() {
+        assert_eq!(Anno::new(0).unwrap().to_string(), "00");
+        assert_eq!(Anno::new(121).unwrap().to_string(), "Vxi");
+        assert_eq!(Anno::new(88).unwrap().to_string(), "IV0");
+        assert_eq!(Anno::new(-1).unwrap().to_string(), "0i");
+        assert!(Anno::new(-1).unwrap().is_before_era());
+        assert_eq!(Anno::new(-1).unwrap().era_abbreviation(), "a.e.n.");
+        assert_eq!(Anno::new(0).unwrap().era_abbreviation(), "e.n.");
+    }
+
+    #[test]
+    fn reads_numerals_in_either_case() {
+        assert_eq!("Vxi".parse::<Anno>().unwrap().years(), 121);
+        assert_eq!("V xi".parse::<Anno>().unwrap().years(), 121);
+        assert_eq!("V.xi".parse::<Anno>().unwrap().years(), 121);
+        assert_eq!("IV0".parse::<Anno>().unwrap().years(), 88);
+        assert_eq!("0i".parse::<Anno>().unwrap().years(), 1);
+        assert_eq!("vxi".parse::<Anno>().unwrap().years(), 121);
+        assert_eq!("VXI".parse::<Anno>().unwrap().years(), 121);
+    }
+
+    #[test]
+    fn rejects_malformed_numerals() {
+        assert!("".parse::<Anno>().is_err());
+        assert!("æ".parse::<Anno>().is_err());
+        assert!("Vxí".parse::<Anno>().is_err());
+        assert!("☉".parse::<Anno>().is_err());
+        assert!("IIII".parse::<Anno>().is_err());
+        assert!("Vxxii".parse::<Anno>().is_err());
+    }
+
+    #[test]
+    fn numerals_round_trip() {
+        for years in (0..2000).chain([87_999]) {
+            let anno = Anno::new(years).unwrap();
+            assert_eq!(anno.numeral().parse::<Anno>().unwrap(), anno, "{}", anno);
+        }
+        for n in 1..=MAX_CYCLE {
+            assert_eq!(parse_roman(&roman(n)), Some(n));
+        }
+    }
+}
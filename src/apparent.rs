@@ -0,0 +1,150 @@
+use astro::{lunar, nutation, sun};
+
+use crate::{Planet, ThelemicDate};
+
+/// Constant of aberration, in arcseconds.
+const ABERRATION: f64 = 20.495_52;
+
+impl ThelemicDate {
+    /// ΔT, the amount Terrestrial Time runs ahead of Universal Time, at
+    /// Julian Day `jd`, in days. Modelled by Espenak and Meeus's polynomials,
+    /// fitted to observations in the past and extrapolated into the future,
+    /// evaluated at the fractional year itself so that ΔT runs smoothly
+    /// through each year, before year 0 as after it.
+    pub fn delta_t(jd: f64) -> f64 {
+        let y = 2000.0 + (jd - 2_451_545.0) / 365.25;
+        let seconds = match y {
+            y if y < -500.0 => long_term(y),
+            y if y < 500.0 => polynomial(y / 100.0, &[
+                10_583.6, -1_014.41, 33.783_11, -5.952_053, -0.179_845_2, 0.022_174_192, 0.009_031_652_1,
+            ]),
+            y if y < 1600.0 => polynomial((y - 1000.0) / 100.0, &[
+                1_574.2, -556.01, 71.234_72, 0.319_781, -0.850_346_3, -0.005_050_998, 0.008_357_207_3,
+            ]),
+            y if y < 1700.0 => polynomial(y - 1600.0, &[120.0, -0.9808, -0.015_32, 1.0 / 7129.0]),
+            y if y < 1800.0 => polynomial(y - 1700.0, &[8.83, 0.1603, -0.005_928_5, 0.000_133_36, -1.0 / 1_174_000.0]),
+            y if y < 1860.0 => polynomial(y - 1800.0, &[
+                13.72, -0.332_447, 0.006_861_2, 0.004_111_6, -0.000_374_36, 0.000_012_127_2, -0.000_000_169_9,
+                0.000_000_000_875,
+            ]),
+            y if y < 1900.0 => polynomial(y - 1860.0, &[
+                7.62, 0.5737, -0.251_754, 0.016_806_68, -0.000_447_362_4, 1.0 / 233_174.0,
+            ]),
+            y if y < 1920.0 => polynomial(y - 1900.0, &[-2.79, 1.494_119, -0.059_893_9, 0.006_196_6, -0.000_197]),
+            y if y < 1941.0 => polynomial(y - 1920.0, &[21.20, 0.844_93, -0.076_100, 0.002_093_6]),
+            y if y < 1961.0 => polynomial(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+            y if y < 1986.0 => polynomial(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+            y if y < 2005.0 => polynomial(y - 2000.0, &[
+                63.86, 0.3345, -0.060_374, 0.001_727_5, 0.000_651_814, 0.000_023_735_99,
+            ]),
+            y if y < 2050.0 => polynomial(y - 2000.0, &[62.92, 0.322_17, 0.005_589]),
+            y if y < 2150.0 => long_term(y) - 0.5628 * (2150.0 - y),
+            y => long_term(y),
+        };
+        seconds / 86400.0
+    }
+
+    /// The Julian Ephemeris Day (Terrestrial Time) the theories are evaluated
+    /// at for Julian Day `jd` (Universal Time), or `jd` itself for geometric
+    /// positions.
+    pub(crate) fn ephemeris_day(&self, jd: f64) -> f64 {
+        if self.geometric { jd } else { jd + Self::delta_t(jd) }
+    }
+
+    /// The Sun's apparent longitude at Julian Ephemeris Day `jde`, in radians.
+    pub(crate) fn apparent_sun_longitude(jde: f64) -> f64 {
+        let (sun_pos, sun_dist) = sun::geocent_ecl_pos(jde);
+        let (nut_in_long, _nut_in_oblq) = nutation::nutation(jde);
+        // Meeus, eq. 25.10
+        let aberration = (-20.4898 / 3600.0_f64).to_radians() / sun_dist;
+        sun_pos.long + nut_in_long + aberration
+    }
+
+    /// The Sun's geocentric ecliptic longitude at Julian Day `jd`, in
+    /// radians: apparent unless geometric positions were asked for.
+    pub fn sun_longitude(&self, jd: f64) -> f64 {
+        let jde = self.ephemeris_day(jd);
+        if self.geometric {
+            sun::geocent_ecl_pos(jde).0.long
+        } else {
+            Self::apparent_sun_longitude(jde)
+        }
+    }
+
+    /// The Moon's geocentric ecliptic longitude at Julian Day `jd`, in
+    /// radians: apparent unless geometric positions were asked for. The
+    /// Moon's aberration is under an arcsecond and left out.
+    pub fn moon_longitude(&self, jd: f64) -> f64 {
+        let jde = self.ephemeris_day(jd);
+        let (moon_pos, _moon_dist) = lunar::geocent_ecl_pos(jde);
+        if self.geometric {
+            moon_pos.long
+        } else {
+            moon_pos.long + nutation::nutation(jde).0
+        }
+    }
+
+    /// A planet's geocentric ecliptic longitude at Julian Day `jd`, in
+    /// radians: apparent unless geometric positions were asked for, and
+    /// `None` where its theory does not reach.
+    pub fn planet_longitude(&self, planet: Planet, jd: f64) -> Option<f64> {
+        let jde = self.ephemeris_day(jd);
+        let (long, lat) = planet.geocent_ecl_coords(jde)?;
+        if self.geometric {
+            return Some(long);
+        }
+
+        // Annual aberration (Meeus, eq. 23.2, without the small term for
+        // the eccentricity of the Earth's orbit)
+        let sun_long = sun::geocent_ecl_pos(jde).0.long;
+        let aberration = -(ABERRATION / 3600.0).to_radians() * (sun_long - long).cos() / lat.cos();
+        Some(long + nutation::nutation(jde).0 + aberration)
+    }
+}
+
+/// Morrison and Stephenson's long-term parabola for ΔT, in seconds.
+fn long_term(y: f64) -> f64 {
+    let u = (y - 1820.0) / 100.0;
+    32.0 * u * u - 20.0
+}
+
+/// Evaluates a polynomial given its coefficients from the constant term up.
+fn polynomial(t: f64, coefficients: &[f64]) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, coefficient| sum * t + coefficient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Julian Day at a fractional year, counted as `delta_t` counts it.
+    fn jd_of(year: f64) -> f64 {
+        2_451_545.0 + (year - 2000.0) * 365.25
+    }
+
+    fn seconds(year: f64) -> f64 {
+        ThelemicDate::delta_t(jd_of(year)) * 86400.0
+    }
+
+    #[test]
+    fn matches_reference_epochs() {
+        // Morrison and Stephenson's values as tabulated by Espenak and Meeus
+        for (year, expected, tolerance) in [
+            (-500.0, 17_190.0, 20.0),
+            (1000.0, 1_574.0, 5.0),
+            (1900.0, -2.8, 1.0),
+            (2000.0, 63.9, 1.0),
+        ] {
+            let found = seconds(year);
+            assert!((found - expected).abs() < tolerance, "ΔT in {} was {:.1}s, expected {}s", year, found, expected);
+        }
+    }
+
+    #[test]
+    fn runs_forward_within_years_before_zero() {
+        // ΔT falls steadily through antiquity, so it must fall month by month
+        let months: Vec<f64> = (0..12).map(|month| seconds(-500.0 + (month as f64 + 0.5) / 12.0)).collect();
+        assert!(months.windows(2).all(|pair| pair[1] < pair[0]), "{:?}", months);
+        assert!((seconds(-499.99) - seconds(-500.01)).abs() < 1.0);
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+/// A geocoding result remembered between runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// IANA timezone name
+    pub timezone: String,
+    /// The geocoder that produced the entry, see `Geocoder::source`
+    #[serde(default)]
+    pub source: String,
+    /// When the location was geocoded, in seconds since the Unix epoch
+    pub resolved_at: u64,
+}
+
+/// On-disk cache of geocoded place names, keyed on the normalized query, so
+/// that repeated runs do not hit the geocoding service.
+#[derive(Debug, Clone)]
+pub struct LocationCache {
+    path: PathBuf,
+    ttl: Duration,
+}
+
+impl LocationCache {
+    /// How long a cached location is trusted before it is geocoded again.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+    pub fn new(path: PathBuf, ttl: Duration) -> Self {
+        LocationCache { path, ttl }
+    }
+
+    /// Opens the cache file in the user's cache directory, or returns `None`
+    /// when no such directory can be determined.
+    pub fn open_default() -> Option<Self> {
+        let dir = Self::cache_dir()?;
+        Some(Self::new(dir.join("tdate").join("locations.json"), Self::DEFAULT_TTL))
+    }
+
+    fn cache_dir() -> Option<PathBuf> {
+        let var = |name: &str| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+        if cfg!(windows) {
+            var("LOCALAPPDATA")
+        } else if cfg!(target_os = "macos") {
+            var("HOME").map(|home| home.join("Library").join("Caches"))
+        } else {
+            var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache")))
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Lower-cases a query and collapses its whitespace, so that "Las Vegas,NV"
+    /// and "las vegas, nv" share an entry.
+    pub fn normalize(query: &str) -> String {
+        query.split(',')
+            .map(|part| part.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join(", ")
+            .to_lowercase()
+    }
+
+    /// Returns the entry for a query if it came from `source` and has not expired.
+    pub fn get(&self, query: &str, source: &str) -> Option<CachedLocation> {
+        self.get_stale(query, source).filter(|entry| !self.is_expired(entry))
+    }
+
+    /// Returns the entry for a query if it came from `source`, however old,
+    /// for when the place cannot be geocoded again.
+    pub fn get_stale(&self, query: &str, source: &str) -> Option<CachedLocation> {
+        let entry = self.entries().remove(&Self::normalize(query))?;
+        (entry.source == source).then_some(entry)
+    }
+
+    /// Stores a freshly geocoded location.
+    pub fn insert(&self, query: &str, latitude: f64, longitude: f64, timezone: &str, source: &str)
+        -> Result<(), Box<dyn std::error::Error>> {
+        let mut entries = self.entries();
+        entries.insert(Self::normalize(query), CachedLocation {
+            latitude,
+            longitude,
+            timezone: timezone.to_string(),
+            source: source.to_string(),
+            resolved_at: Self::now(),
+        });
+        self.write(&entries)
+    }
+
+    /// Every entry in the cache, expired or not. An unreadable cache file is
+    /// treated as empty.
+    pub fn entries(&self) -> BTreeMap<String, CachedLocation> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_expired(&self, entry: &CachedLocation) -> bool {
+        Self::now().saturating_sub(entry.resolved_at) > self.ttl.as_secs()
+    }
+
+    /// Removes every entry, returning how many there were.
+    pub fn clear(&self) -> Result<usize, Box<dyn std::error::Error>> {
+        let count = self.entries().len();
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(count),
+        }
+    }
+
+    fn write(&self, entries: &BTreeMap<String, CachedLocation>) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Write to a temporary file first so a concurrent run never reads half a file
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(entries)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0)
+    }
+}
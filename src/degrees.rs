@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+/// The smallest unit a degree within a sign is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Precision {
+    /// Whole degrees, e.g. "1º"
+    #[default]
+    Whole,
+    /// Degrees and minutes of arc, e.g. "1º23'"
+    Minutes,
+    /// Degrees, minutes and seconds of arc, e.g. "1º23'45\""
+    Seconds,
+    /// Decimal degrees to the given number of places (at most 9), e.g. "1.39º"
+    Decimal(u32),
+}
+
+impl Precision {
+    /// Number of the smallest unit in one degree.
+    fn units_per_degree(&self) -> u64 {
+        match self {
+            Precision::Whole => 1,
+            Precision::Minutes => 60,
+            Precision::Seconds => 3600,
+            Precision::Decimal(places) => 10u64.pow(*places),
+        }
+    }
+}
+
+impl FromStr for Precision {
+    type Err = Box<dyn std::error::Error>;
+
+    /// Parses "whole", "minutes", "seconds", "decimal" (two places) or
+    /// "decimal:N", with "d", "dm" and "dms" standing for the first three.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        match lower.as_str() {
+            "whole" | "d" => Ok(Precision::Whole),
+            "minutes" | "dm" => Ok(Precision::Minutes),
+            "seconds" | "dms" => Ok(Precision::Seconds),
+            "decimal" => Ok(Precision::Decimal(2)),
+            _ => match lower.strip_prefix("decimal:").map(|places| places.parse::<u32>()) {
+                Some(Ok(places)) if places <= 9 => Ok(Precision::Decimal(places)),
+                Some(_) => Err(format!("Invalid number of decimal places: {} (expected 0 to 9)", s).into()),
+                None => Err(format!(
+                    "Unknown degree precision: {} (expected whole, minutes, seconds, decimal or decimal:N)", s
+                ).into()),
+            },
+        }
+    }
+}
+
+/// How the degree a body has reached within its sign is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DegreeFormat {
+    pub precision: Precision,
+    /// Round to the nearest unit of the precision rather than truncating, so
+    /// 0º59'50" is "1º" in whole degrees; a body rounded up to 30º is written
+    /// as 0º of the next sign
+    pub round: bool,
+    /// Count degrees from 1 to 30, naming the degree being passed through,
+    /// rather than from 0 to 29. Whole ordinal degrees are never rounded, as
+    /// the first degree runs all the way from 0º to 1º. Only whole degrees
+    /// are counted this way; finer precisions write the angle itself
+    pub ordinal: bool,
+}
+
+impl DegreeFormat {
+    /// Splits a longitude in degrees into the index of its sign in
+    /// `ThelemicDate::SIGNS`, the whole degree within the sign as written and
+    /// the full text of the degree, e.g. `(4, 1, "1º23'")`.
+    pub fn split(&self, longitude: f64) -> (usize, i32, String) {
+        let per_degree = self.precision.units_per_degree();
+        let scaled = longitude.rem_euclid(360.0) * per_degree as f64;
+        let ordinal = self.ordinal && self.precision == Precision::Whole;
+        let round = self.round && !ordinal;
+        let units = if round { scaled.round() } else { scaled.floor() } as u64 % (360 * per_degree);
+
+        let sign = (units / (30 * per_degree)) as usize;
+        let within = units % (30 * per_degree);
+        let degree = (within / per_degree) as i32 + i32::from(ordinal);
+        let fraction = within % per_degree;
+        let text = match self.precision {
+            Precision::Whole | Precision::Decimal(0) => format!("{}º", degree),
+            Precision::Minutes => format!("{}º{:02}'", degree, fraction),
+            Precision::Seconds => format!("{}º{:02}'{:02}\"", degree, fraction / 60, fraction % 60),
+            Precision::Decimal(places) => format!("{}.{:0width$}º", degree, fraction, width = places as usize),
+        };
+        (sign, degree, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(precision: Precision, round: bool, ordinal: bool, longitude: f64) -> (usize, i32, String) {
+        DegreeFormat { precision, round, ordinal }.split(longitude)
+    }
+
+    #[test]
+    fn writes_each_precision() {
+        let longitude = 121.0 + 23.0 / 60.0 + 45.0 / 3600.0;
+        assert_eq!(split(Precision::Whole, false, false, longitude), (4, 1, "1º".to_string()));
+        assert_eq!(split(Precision::Minutes, false, false, longitude), (4, 1, "1º23'".to_string()));
+        assert_eq!(split(Precision::Seconds, true, false, longitude), (4, 1, "1º23'45\"".to_string()));
+        assert_eq!(split(Precision::Decimal(2), false, false, longitude), (4, 1, "1.39º".to_string()));
+        assert_eq!(split(Precision::Decimal(0), false, false, longitude), (4, 1, "1º".to_string()));
+    }
+
+    #[test]
+    fn truncates_unless_rounding() {
+        assert_eq!(split(Precision::Whole, false, false, 120.85), (4, 0, "0º".to_string()));
+        assert_eq!(split(Precision::Whole, true, false, 120.85), (4, 1, "1º".to_string()));
+        assert_eq!(split(Precision::Minutes, true, false, 120.0 + 59.995 / 60.0), (4, 1, "1º00'".to_string()));
+    }
+
+    #[test]
+    fn rounds_up_to_thirty_into_the_next_sign() {
+        assert_eq!(split(Precision::Whole, true, false, 29.6), (1, 0, "0º".to_string()));
+        assert_eq!(split(Precision::Minutes, true, false, 59.999), (2, 0, "0º00'".to_string()));
+        assert_eq!(split(Precision::Decimal(1), true, false, 359.96), (0, 0, "0.0º".to_string()));
+        assert_eq!(split(Precision::Whole, false, false, 29.6), (0, 29, "29º".to_string()));
+    }
+
+    #[test]
+    fn counts_ordinal_whole_degrees_only() {
+        assert_eq!(split(Precision::Whole, false, true, 120.0), (4, 1, "1º".to_string()));
+        assert_eq!(split(Precision::Whole, false, true, 120.85), (4, 1, "1º".to_string()));
+        // Never rounded into the next degree or sign
+        assert_eq!(split(Precision::Whole, true, true, 149.99), (4, 30, "30º".to_string()));
+        // Finer precisions give the angle itself
+        assert_eq!(split(Precision::Minutes, false, true, 120.85), (4, 0, "0º51'".to_string()));
+        assert_eq!(split(Precision::Seconds, false, true, 120.5), (4, 0, "0º30'00\"".to_string()));
+        assert_eq!(split(Precision::Decimal(2), true, true, 120.855), (4, 0, "0.86º".to_string()));
+    }
+
+    #[test]
+    fn wraps_negative_and_boundary_longitudes() {
+        assert_eq!(split(Precision::Whole, false, false, 0.0), (0, 0, "0º".to_string()));
+        assert_eq!(split(Precision::Whole, false, false, 30.0), (1, 0, "0º".to_string()));
+        assert_eq!(split(Precision::Whole, false, false, 360.0), (0, 0, "0º".to_string()));
+        assert_eq!(split(Precision::Whole, false, false, 720.5), (0, 0, "0º".to_string()));
+        assert_eq!(split(Precision::Whole, false, false, -0.5), (11, 29, "29º".to_string()));
+        assert_eq!(split(Precision::Minutes, false, false, -30.0), (11, 0, "0º00'".to_string()));
+    }
+
+    #[test]
+    fn parses_precisions() {
+        assert_eq!("dms".parse::<Precision>().unwrap(), Precision::Seconds);
+        assert_eq!("Decimal".parse::<Precision>().unwrap(), Precision::Decimal(2));
+        assert_eq!("decimal:4".parse::<Precision>().unwrap(), Precision::Decimal(4));
+        assert!("decimal:10".parse::<Precision>().is_err());
+        assert!("arcminutes".parse::<Precision>().is_err());
+    }
+}
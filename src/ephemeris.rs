@@ -0,0 +1,194 @@
+use chrono::{DateTime, Duration};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::{ResolvedLocation, ThelemicDate};
+
+/// Column names of the CSV output, in the order of `EphemerisRow`'s fields.
+const CSV_HEADER: &str = "datetime,julian_day,sun_longitude,sun_sign,sun_degree,\
+moon_longitude,moon_sign,moon_degree,weekday,anno,anno_years";
+
+/// The Thelemic date at one step of an ephemeris.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EphemerisRow {
+    /// Local date and time in RFC 3339 form, e.g. "2025-03-20T00:00:00+00:00"
+    pub datetime: String,
+    pub julian_day: f64,
+    /// Degrees within [0, 360)
+    pub sun_longitude: f64,
+    pub sun_sign: &'static str,
+    pub sun_degree: i32,
+    /// Degrees within [0, 360)
+    pub moon_longitude: f64,
+    pub moon_sign: &'static str,
+    pub moon_degree: i32,
+    /// Latin name of the weekday, e.g. "Mercurii"
+    pub weekday: &'static str,
+    /// Anno numeral, e.g. "Vxi"
+    pub anno: String,
+    /// Signed years since the Equinox of the Gods, negative before the era
+    pub anno_years: i32,
+    /// Latin name of the ruler of the planetary hour, e.g. "Solis"; only
+    /// found when asked for with `ThelemicDate::with_planetary_hour`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planetary_hour: Option<&'static str>,
+}
+
+/// Ways of writing out an ephemeris.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EphemerisFormat {
+    Csv,
+    /// A single JSON array of rows
+    Json,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl FromStr for EphemerisFormat {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(EphemerisFormat::Csv),
+            "json" => Ok(EphemerisFormat::Json),
+            "jsonl" | "jsonlines" | "json-lines" | "ndjson" => Ok(EphemerisFormat::JsonLines),
+            _ => Err(format!("Unknown output format: {} (expected csv, json or jsonl)", s).into()),
+        }
+    }
+}
+
+impl EphemerisFormat {
+    /// Writes rows out in this format.
+    pub fn write(&self, rows: &[EphemerisRow], out: &mut impl Write) -> io::Result<()> {
+        match self {
+            EphemerisFormat::Csv => {
+                // The planetary hour column is only there when it was asked for
+                let hours = rows.iter().any(|row| row.planetary_hour.is_some());
+                writeln!(out, "{}{}", CSV_HEADER, if hours { ",planetary_hour" } else { "" })?;
+                for row in rows {
+                    write!(
+                        out,
+                        "{},{:.6},{:.6},{},{},{:.6},{},{},{},{},{}",
+                        row.datetime, row.julian_day,
+                        row.sun_longitude, row.sun_sign, row.sun_degree,
+                        row.moon_longitude, row.moon_sign, row.moon_degree,
+                        row.weekday, row.anno, row.anno_years
+                    )?;
+                    if hours {
+                        write!(out, ",{}", row.planetary_hour.unwrap_or_default())?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+            EphemerisFormat::Json => {
+                serde_json::to_writer_pretty(&mut *out, rows)?;
+                writeln!(out)?;
+            }
+            EphemerisFormat::JsonLines => {
+                for row in rows {
+                    serde_json::to_writer(&mut *out, row)?;
+                    writeln!(out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a step such as "1d", "6h", "30m", "90s" or "1w".
+pub fn parse_step(s: &str) -> Result<Duration, Box<dyn std::error::Error>> {
+    let s = s.trim();
+    let invalid = || format!("Invalid step: {} (expected e.g. 1d, 6h, 30m)", s);
+    let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let count: i64 = s[..split].parse().map_err(|_| invalid())?;
+    let step = match &s[split..] {
+        "w" => Duration::try_weeks(count),
+        "d" => Duration::try_days(count),
+        "h" => Duration::try_hours(count),
+        "m" | "min" => Duration::try_minutes(count),
+        "s" => Duration::try_seconds(count),
+        _ => return Err(invalid().into()),
+    }.ok_or_else(|| format!("Step out of range: {}", s))?;
+    if step <= Duration::zero() {
+        return Err("The step must be longer than zero".into());
+    }
+    Ok(step)
+}
+
+impl ThelemicDate {
+    /// Computes the Thelemic date at a location every `step` from `from` up
+    /// to, but not including, `to`. Steps are taken in UTC, so every instant
+    /// appears exactly once across DST changes; each row gives its time on
+    /// the local clock.
+    pub fn ephemeris(&self, from: &DateTime<Tz>, to: &DateTime<Tz>, step: Duration, location: &ResolvedLocation)
+        -> Result<Vec<EphemerisRow>, Box<dyn std::error::Error>> {
+        if step <= Duration::zero() {
+            return Err("The step must be longer than zero".into());
+        }
+
+        let mut rows = Vec::new();
+        let mut dt = from.with_timezone(&location.timezone);
+        while dt < *to {
+            let value = self.at_location(&dt, location)?;
+            rows.push(EphemerisRow {
+                datetime: dt.to_rfc3339(),
+                julian_day: value.julian_day,
+                sun_longitude: value.sun.longitude,
+                sun_sign: value.sun.sign,
+                sun_degree: value.sun.degree,
+                moon_longitude: value.moon.longitude,
+                moon_sign: value.moon.sign,
+                moon_degree: value.moon.degree,
+                weekday: value.weekday_name(),
+                anno: value.anno.numeral(),
+                anno_years: value.anno.years(),
+                planetary_hour: value.planetary_hour.map(|hour| hour.ruler_name()),
+            });
+            dt += step;
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::London;
+
+    #[test]
+    fn parses_steps() {
+        assert_eq!(parse_step("1d").unwrap(), Duration::days(1));
+        assert_eq!(parse_step(" 6h ").unwrap(), Duration::hours(6));
+        assert_eq!(parse_step("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_step("30min").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_step("90s").unwrap(), Duration::seconds(90));
+        assert_eq!(parse_step("2w").unwrap(), Duration::weeks(2));
+    }
+
+    #[test]
+    fn rejects_malformed_steps() {
+        for step in ["", "d", "1", "1y", "-1d", "1.5h", "0h", "1 d", "99999999999999999w", "½d"] {
+            assert!(parse_step(step).is_err(), "{}", step);
+        }
+    }
+
+    #[test]
+    fn steps_through_a_clock_change_once() {
+        let location = ResolvedLocation { latitude: None, longitude: None, timezone: London };
+        // The clocks go forward from 01:00 GMT to 02:00 BST
+        let from = London.with_ymd_and_hms(2025, 3, 30, 0, 0, 0).unwrap();
+        let to = London.with_ymd_and_hms(2025, 3, 30, 4, 0, 0).unwrap();
+        let rows = ThelemicDate::new().with_cache(None)
+            .ephemeris(&from, &to, Duration::hours(1), &location)
+            .unwrap();
+        let times: Vec<_> = rows.iter().map(|row| row.datetime.as_str()).collect();
+        assert_eq!(times, [
+            "2025-03-30T00:00:00+00:00",
+            "2025-03-30T02:00:00+01:00",
+            "2025-03-30T03:00:00+01:00",
+        ]);
+    }
+}
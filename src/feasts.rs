@@ -0,0 +1,199 @@
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::fmt;
+
+use crate::{Anno, ThelemicDate};
+
+/// The holy days of the Thelemic year, after Liber AL II:36–43.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Observance {
+    /// 20 March, the ritual of 1904 that opened the Equinox of the Gods
+    SupremeRitual,
+    /// The vernal equinox, which begins each Anno
+    EquinoxOfTheGods,
+    /// 8, 9 and 10 April, numbered 1 to 3
+    WritingOfTheBook(u8),
+    JuneSolstice,
+    /// 12 August, the wedding of Aleister Crowley and Rose Kelly in 1903
+    ProphetAndBride,
+    SeptemberEquinox,
+    /// 12 October, Aleister Crowley's birthday
+    BirthOfTheProphet,
+    /// 1 December, the greater feast for Aleister Crowley's death
+    GreaterFeastOfTheProphet,
+    DecemberSolstice,
+}
+
+impl Observance {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Observance::SupremeRitual => "Feast of the Supreme Ritual",
+            Observance::EquinoxOfTheGods => "Feast of the Equinox of the Gods",
+            Observance::WritingOfTheBook(1) => "First Day of the Writing of the Book of the Law",
+            Observance::WritingOfTheBook(2) => "Second Day of the Writing of the Book of the Law",
+            Observance::WritingOfTheBook(_) => "Third Day of the Writing of the Book of the Law",
+            Observance::JuneSolstice => "Feast of the Times: June Solstice",
+            Observance::ProphetAndBride => "Feast of the First Night of the Prophet and His Bride",
+            Observance::SeptemberEquinox => "Feast of the Times: September Equinox",
+            Observance::BirthOfTheProphet => "Birthday of the Prophet",
+            Observance::GreaterFeastOfTheProphet => "Greater Feast of the Prophet",
+            Observance::DecemberSolstice => "Feast of the Times: December Solstice",
+        }
+    }
+
+    /// The ecliptic longitude of the Sun that fixes the feast, in degrees,
+    /// for those kept at an equinox or solstice.
+    fn solar_longitude(&self) -> Option<f64> {
+        match self {
+            Observance::EquinoxOfTheGods => Some(0.0),
+            Observance::JuneSolstice => Some(90.0),
+            Observance::SeptemberEquinox => Some(180.0),
+            Observance::DecemberSolstice => Some(270.0),
+            _ => None,
+        }
+    }
+
+    /// The month and day of the feasts kept on a fixed Gregorian date.
+    fn fixed_date(&self) -> Option<(u32, u32)> {
+        match self {
+            Observance::SupremeRitual => Some((3, 20)),
+            Observance::WritingOfTheBook(day) => Some((4, 7 + *day as u32)),
+            Observance::ProphetAndBride => Some((8, 12)),
+            Observance::BirthOfTheProphet => Some((10, 12)),
+            Observance::GreaterFeastOfTheProphet => Some((12, 1)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Observance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A holy day as it falls in a particular year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feast {
+    pub observance: Observance,
+    /// Local date of the feast
+    pub date: NaiveDate,
+    /// The moment of the equinox or solstice, for the feasts kept at one,
+    /// given as the first whole minute after it so that the Sun already
+    /// stands in its new sign
+    pub time: Option<DateTime<Tz>>,
+}
+
+impl Feast {
+    /// The instant the feast's Thelemic date is given for: the equinox or
+    /// solstice itself, or local noon for the feasts on fixed dates.
+    pub fn moment(&self, tz: &Tz) -> Result<DateTime<Tz>, Box<dyn std::error::Error>> {
+        match self.time {
+            Some(time) => Ok(time),
+            None => {
+                let noon = self.date.and_time(NaiveTime::from_hms_opt(12, 0, 0).expect("Noon is a valid time"));
+                tz.from_local_datetime(&noon).earliest().ok_or_else(|| "Local noon does not exist".into())
+            }
+        }
+    }
+}
+
+impl ThelemicDate {
+    /// Every observance, in the order they fall in a year.
+    pub const OBSERVANCES: [Observance; 11] = [
+        Observance::SupremeRitual,
+        Observance::EquinoxOfTheGods,
+        Observance::WritingOfTheBook(1),
+        Observance::WritingOfTheBook(2),
+        Observance::WritingOfTheBook(3),
+        Observance::JuneSolstice,
+        Observance::ProphetAndBride,
+        Observance::SeptemberEquinox,
+        Observance::BirthOfTheProphet,
+        Observance::GreaterFeastOfTheProphet,
+        Observance::DecemberSolstice,
+    ];
+
+    /// Lists the feasts of an Anno in the observer's timezone, from the
+    /// Supreme Ritual on 20 March, which opens the Equinox of the Gods,
+    /// through the December solstice. Equinoxes and solstices are found
+    /// astronomically.
+    pub fn feasts(anno: Anno, tz: &Tz) -> Result<Vec<Feast>, Box<dyn std::error::Error>> {
+        let year = anno.gregorian_year();
+        Self::check_year(year)?;
+
+        let mut feasts = Self::OBSERVANCES.iter()
+            .map(|&observance| {
+                if let Some(longitude) = observance.solar_longitude() {
+                    let time: DateTime<Utc> = Self::utc_from_julian_day(Self::solar_longitude_jd(year, longitude))
+                        .ok_or("Feast out of range")?;
+                    let time = (time.duration_trunc(Duration::minutes(1))? + Duration::minutes(1)).with_timezone(tz);
+                    Ok(Feast { observance, date: time.date_naive(), time: Some(time) })
+                } else {
+                    let (month, day) = observance.fixed_date().expect("Every other feast has a fixed date");
+                    let date = NaiveDate::from_ymd_opt(year, month, day).ok_or("Feast out of range")?;
+                    Ok(Feast { observance, date, time: None })
+                }
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+        // The equinox can fall on the 19th or 21st, either side of the Supreme Ritual
+        feasts.sort_by_key(|feast| (feast.date, feast.time.is_some()));
+        Ok(feasts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::{America::Los_Angeles, Europe::London, Pacific::Auckland, UTC};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn lists_the_feasts_of_an_anno() {
+        // Anno Vxi, 2025 e.v.
+        let feasts = ThelemicDate::feasts(Anno::new(121).unwrap(), &London).unwrap();
+        let observances: Vec<_> = feasts.iter().map(|feast| feast.observance).collect();
+        assert_eq!(observances, ThelemicDate::OBSERVANCES);
+        let dates: Vec<_> = feasts.iter().map(|feast| feast.date).collect();
+        assert_eq!(dates, [
+            date(2025, 3, 20), date(2025, 3, 20), date(2025, 4, 8), date(2025, 4, 9), date(2025, 4, 10),
+            date(2025, 6, 21), date(2025, 8, 12), date(2025, 9, 22), date(2025, 10, 12), date(2025, 12, 1),
+            date(2025, 12, 21),
+        ]);
+
+        // Published equinoxes and solstices, to the minute
+        let published = [
+            UTC.with_ymd_and_hms(2025, 3, 20, 9, 1, 0).unwrap(),
+            UTC.with_ymd_and_hms(2025, 6, 21, 2, 42, 0).unwrap(),
+            UTC.with_ymd_and_hms(2025, 9, 22, 18, 19, 0).unwrap(),
+            UTC.with_ymd_and_hms(2025, 12, 21, 15, 3, 0).unwrap(),
+        ];
+        let times: Vec<_> = feasts.iter().filter_map(|feast| feast.time).collect();
+        assert_eq!(times.len(), published.len());
+        for (time, published) in times.into_iter().zip(published) {
+            assert_eq!(time.timestamp() % 60, 0);
+            assert!((time.with_timezone(&UTC) - published).num_seconds().abs() <= 120, "{}", time);
+        }
+
+        // Feasts on fixed dates are given for local noon
+        let noon = feasts[6].moment(&London).unwrap();
+        assert_eq!(noon, London.with_ymd_and_hms(2025, 8, 12, 12, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn dates_the_equinoxes_and_solstices_locally() {
+        // The December solstice of 2025 falls on the 22nd in New Zealand
+        let feasts = ThelemicDate::feasts(Anno::new(121).unwrap(), &Auckland).unwrap();
+        let solstice = feasts.last().unwrap();
+        assert_eq!((solstice.observance, solstice.date), (Observance::DecemberSolstice, date(2025, 12, 22)));
+
+        // The equinox of 2024 fell on the evening of 19 March in California,
+        // before the Supreme Ritual
+        let feasts = ThelemicDate::feasts(Anno::new(120).unwrap(), &Los_Angeles).unwrap();
+        assert_eq!((feasts[0].observance, feasts[0].date), (Observance::EquinoxOfTheGods, date(2024, 3, 19)));
+        assert_eq!((feasts[1].observance, feasts[1].date), (Observance::SupremeRitual, date(2024, 3, 20)));
+    }
+}
@@ -0,0 +1,144 @@
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::ThelemicDateValue;
+
+/// Ways of printing a computed date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The usual date line
+    Text,
+    Json,
+    Toml,
+    /// One `key=value` pair per line, with dotted keys for nested values,
+    /// e.g. `sun.sign=Leo`
+    Kv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "toml" => Ok(OutputFormat::Toml),
+            "kv" => Ok(OutputFormat::Kv),
+            _ => Err(format!("Unknown format: {} (expected text, json, toml or kv)", s).into()),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Renders a date in this format, without a trailing newline.
+    pub fn render(&self, value: &ThelemicDateValue) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            OutputFormat::Text => Ok(value.to_string()),
+            OutputFormat::Json => Ok(serde_json::to_string_pretty(value)?),
+            OutputFormat::Toml => Ok(toml::to_string(value)?.trim_end().to_string()),
+            OutputFormat::Kv => {
+                let mut lines = Vec::new();
+                flatten("", &serde_json::to_value(value)?, &mut lines);
+                Ok(lines.join("\n"))
+            }
+        }
+    }
+}
+
+/// Appends `key=value` lines for a JSON value, joining nested keys and array
+/// indices with dots.
+fn flatten(prefix: &str, value: &Value, lines: &mut Vec<String>) {
+    let key = |name: &str| if prefix.is_empty() { name.to_string() } else { format!("{}.{}", prefix, name) };
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                flatten(&key(name), value, lines);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(&key(&i.to_string()), value, lines);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) => lines.push(format!("{}={}", prefix, s)),
+        other => lines.push(format!("{}={}", prefix, other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ayanamsa, Location, ThelemicDate, Zodiac};
+    use chrono_tz::Europe::London;
+
+    /// The keys of `key=value` lines, in order.
+    fn keys(kv: &str) -> Vec<&str> {
+        kv.lines().map(|line| line.split_once('=').expect("Every line has a key").0).collect()
+    }
+
+    #[test]
+    fn flattens_nested_values_into_dotted_keys() {
+        let value = serde_json::json!({
+            "b": 1,
+            "a": {"sign": "Leo", "skipped": null, "list": [true, {"x": 2.5}]},
+            "text": "a = b",
+        });
+        let mut lines = Vec::new();
+        flatten("", &value, &mut lines);
+        assert_eq!(lines, ["b=1", "a.sign=Leo", "a.list.0=true", "a.list.1.x=2.5", "text=a = b"]);
+    }
+
+    #[test]
+    fn leaves_out_missing_fields() {
+        // A timezone alone gives no coordinates, and nothing optional is asked for
+        let value = ThelemicDate::new().with_cache(None)
+            .in_day(2025, 7, 23, 12, 0, &Location::Timezone(London))
+            .unwrap();
+
+        let kv = OutputFormat::Kv.render(&value).unwrap();
+        assert_eq!(keys(&kv), [
+            "datetime", "timezone", "julian_day", "zodiac", "weekday", "weekday_name",
+            "anno.numeral", "anno.years", "anno.cycle_i", "anno.cycle_ii", "anno.before_era",
+            "sun.longitude", "sun.sign", "sun.degree", "moon.longitude", "moon.sign", "moon.degree",
+        ]);
+        assert!(kv.starts_with("datetime=2025-07-23T12:00:00+01:00\n"));
+        assert!(kv.contains("\nsun.sign=Leo\n"));
+
+        // Plain values come first, then one table for each nested value
+        let toml = OutputFormat::Toml.render(&value).unwrap();
+        let tables: Vec<_> = toml.lines().filter(|line| line.starts_with('[')).collect();
+        assert_eq!(tables, ["[anno]", "[sun]", "[moon]"]);
+        assert!(toml.starts_with("datetime = \"2025-07-23T12:00:00+01:00\"\ntimezone = \"Europe/London\"\n"));
+        assert!(!toml.contains("latitude") && !toml.contains("ayanamsa") && !toml.ends_with('\n'));
+        let parsed: toml::Table = toml.parse().unwrap();
+        assert_eq!(parsed["sun"]["sign"].as_str(), Some("Leo"));
+        assert_eq!(parsed["anno"]["numeral"].as_str(), Some("Vxi"));
+    }
+
+    #[test]
+    fn writes_every_field_asked_for() {
+        let location = Location::Coordinates { latitude: 51.5074, longitude: -0.1278, timezone: Some(London) };
+        let value = ThelemicDate::new().with_cache(None)
+            .with_zodiac(Zodiac::Sidereal(Ayanamsa::Lahiri))
+            .with_planets(true)
+            .with_planetary_hour(true)
+            .in_day(2025, 7, 23, 12, 0, &location)
+            .unwrap();
+
+        let kv = OutputFormat::Kv.render(&value).unwrap();
+        let keys = keys(&kv);
+        assert_eq!(keys[..8], [
+            "datetime", "timezone", "latitude", "longitude", "julian_day", "zodiac", "ayanamsa", "ayanamsa_degrees",
+        ]);
+        assert!(keys.contains(&"planets.0.planet") && keys.contains(&"planets.0.position.sign"));
+        assert!(keys.contains(&"planetary_hour.ruler"));
+        assert!(kv.contains("\nayanamsa=Lahiri\n"));
+
+        let toml = OutputFormat::Toml.render(&value).unwrap();
+        let parsed: toml::Table = toml.parse().unwrap();
+        assert_eq!(parsed["latitude"].as_float(), Some(51.5074));
+        assert_eq!(parsed["planets"][0]["planet"].as_str(), Some("Mercury"));
+        assert_eq!(parsed["planetary_hour"]["number"].as_integer(), Some(6));
+    }
+}
@@ -0,0 +1,219 @@
+/// The embedded city database, one place per line.
+const CITIES: &str = include_str!("../data/cities.csv");
+
+/// Common ways of writing a country that differ from its name or ISO code.
+const COUNTRY_ALIASES: [(&str, &str); 11] = [
+    ("uk", "gb"), ("great britain", "gb"), ("britain", "gb"),
+    ("usa", "us"), ("united states of america", "us"), ("america", "us"),
+    ("holland", "nl"), ("czech republic", "cz"), ("uae", "ae"),
+    ("korea", "kr"), ("drc", "cd")
+];
+
+/// A place in the embedded gazetteer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct City {
+    pub name: &'static str,
+    /// Other names the place is known by, e.g. "München" for Munich
+    pub aliases: Vec<&'static str>,
+    /// First-level administrative region (state, province, ...)
+    pub admin: &'static str,
+    /// Abbreviation of the region, e.g. "NV", if one is in common use
+    pub admin_code: &'static str,
+    pub country: &'static str,
+    /// ISO 3166-1 alpha-2 country code
+    pub country_code: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Approximate population, in thousands
+    pub population: u32,
+}
+
+/// Offline place-name lookup over a built-in list of cities.
+#[derive(Debug, Clone)]
+pub struct Gazetteer {
+    cities: Vec<City>,
+}
+
+impl Gazetteer {
+    /// Loads the city database compiled into the binary.
+    pub fn embedded() -> Self {
+        let cities = CITIES.lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let fields: Vec<&'static str> = line.split(',').collect();
+                City {
+                    name: fields[0],
+                    aliases: fields[1].split('|').filter(|alias| !alias.is_empty()).collect(),
+                    admin: fields[2],
+                    admin_code: fields[3],
+                    country: fields[4],
+                    country_code: fields[5],
+                    latitude: fields[6].parse().expect("Invalid latitude in city database"),
+                    longitude: fields[7].parse().expect("Invalid longitude in city database"),
+                    population: fields[8].parse().expect("Invalid population in city database"),
+                }
+            })
+            .collect();
+        Gazetteer { cities }
+    }
+
+    /// Finds the best match for a query such as "Las Vegas, NV", "london uk"
+    /// or "Munchen". Everything after the first comma (or, failing a match, the
+    /// trailing words) must name the region or country of the place. Small
+    /// misspellings are tolerated, and ties go to the larger city.
+    pub fn search(&self, query: &str) -> Option<&City> {
+        let mut parts = query.split(',').map(normalize).filter(|part| !part.is_empty());
+        let name = parts.next()?;
+        let qualifiers: Vec<String> = parts.collect();
+        if let Some(city) = self.best_match(&name, &qualifiers) {
+            return Some(city);
+        }
+
+        // Without commas, try reading the trailing words as the region or country
+        if qualifiers.is_empty() {
+            let words: Vec<&str> = name.split(' ').collect();
+            for split in (1..words.len()).rev() {
+                let qualifier = [words[split..].join(" ")];
+                if let Some(city) = self.best_match(&words[..split].join(" "), &qualifier) {
+                    return Some(city);
+                }
+            }
+        }
+        None
+    }
+
+    fn best_match(&self, name: &str, qualifiers: &[String]) -> Option<&City> {
+        self.cities.iter()
+            .filter(|city| qualifiers.iter().all(|qualifier| city.is_in(qualifier)))
+            .filter_map(|city| {
+                let distance = std::iter::once(city.name)
+                    .chain(city.aliases.iter().copied())
+                    .map(|candidate| levenshtein(name, &normalize(candidate)))
+                    .min()?;
+                (distance <= tolerance(name)).then_some((distance, city))
+            })
+            .min_by_key(|(distance, city)| (*distance, std::cmp::Reverse(city.population)))
+            .map(|(_, city)| city)
+    }
+}
+
+impl City {
+    /// Whether a normalized qualifier names this city's region or country.
+    fn is_in(&self, qualifier: &str) -> bool {
+        let country_code = self.country_code.to_lowercase();
+        if [self.admin_code, self.country_code].iter().any(|code| normalize(code) == qualifier)
+            || COUNTRY_ALIASES.iter().any(|(alias, code)| *alias == qualifier && *code == country_code) {
+            return true;
+        }
+
+        [self.admin, self.country].iter()
+            .any(|region| levenshtein(qualifier, &normalize(region)) <= tolerance(qualifier))
+    }
+}
+
+/// Number of typos allowed when matching a name of this length. Short names
+/// must match exactly, as a single typo could turn them into another place.
+fn tolerance(name: &str) -> usize {
+    match name.chars().count() {
+        0..=4 => 0,
+        len => len / 4,
+    }
+}
+
+/// Lowercases, folds accented Latin letters to ASCII and drops punctuation,
+/// so that "Zürich" and "zurich" compare equal.
+fn normalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => out.push('a'),
+            'æ' => out.push_str("ae"),
+            'ç' | 'ć' | 'č' => out.push('c'),
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' => out.push('e'),
+            'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => out.push('i'),
+            'ł' => out.push('l'),
+            'ñ' | 'ń' => out.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => out.push('o'),
+            'ś' | 'š' | 'ș' | 'ş' => out.push('s'),
+            'ß' => out.push_str("ss"),
+            'ț' | 'ţ' => out.push('t'),
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' => out.push('u'),
+            'ý' | 'ÿ' => out.push('y'),
+            'ź' | 'ż' | 'ž' => out.push('z'),
+            '-' | '_' => out.push(' '),
+            c if c.is_alphanumeric() || c == ' ' => out.push(c),
+            _ => {}
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Edit distance between two strings, counted in characters.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(query: &str) -> Option<(&'static str, &'static str)> {
+        Gazetteer::embedded().search(query).map(|city| (city.name, city.admin_code))
+    }
+
+    #[test]
+    fn finds_places_by_name_and_alias() {
+        assert_eq!(find("London"), Some(("London", "ENG")));
+        assert_eq!(find("  las-vegas "), Some(("Las Vegas", "NV")));
+        assert_eq!(find("München"), Some(("Munich", "BY")));
+        assert_eq!(find("Munchen"), Some(("Munich", "BY")));
+        assert_eq!(find("ZÜRICH"), Some(("Zurich", "ZH")));
+    }
+
+    #[test]
+    fn narrows_by_region_or_country() {
+        assert_eq!(find("Portland"), Some(("Portland", "OR")));
+        assert_eq!(find("Portland, ME"), Some(("Portland", "ME")));
+        assert_eq!(find("Portland Maine"), Some(("Portland", "ME")));
+        assert_eq!(find("Las Vegas, NV"), Some(("Las Vegas", "NV")));
+        assert_eq!(find("London, UK"), Some(("London", "ENG")));
+        assert_eq!(find("london united kingdom"), Some(("London", "ENG")));
+        assert_eq!(find("London, France"), None);
+    }
+
+    #[test]
+    fn tolerates_small_misspellings() {
+        assert_eq!(find("Sydny"), Some(("Sydney", "NSW")));
+        assert_eq!(find("Las Vgeas"), Some(("Las Vegas", "NV")));
+        // Short names must match exactly
+        assert_eq!(find("Pari"), None);
+        assert_eq!(find("Xanadu"), None);
+        assert_eq!(find(""), None);
+        assert_eq!(find(", UK"), None);
+    }
+
+    #[test]
+    fn normalizes_and_measures_names() {
+        assert_eq!(normalize("Île-de-France"), "ile de france");
+        assert_eq!(normalize("St. Louis,  MO"), "st louis mo");
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("münchen", "munchen"), 1);
+    }
+}
@@ -0,0 +1,200 @@
+use geocoding::{Forward, Openstreetmap, Point};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::LocationCache;
+
+/// A service that turns place names into coordinates.
+pub trait Geocoder {
+    /// Looks up a place, returning its latitude and longitude in degrees, or
+    /// `None` when the provider does not know it.
+    fn forward(&self, query: &str) -> Result<Option<(f64, f64)>, Box<dyn std::error::Error>>;
+
+    /// Identifies the provider and its endpoint. Cached results are only
+    /// reused for the source that produced them.
+    fn source(&self) -> String;
+
+    /// Whether lookups go over the network, and so are cached and refused
+    /// in offline mode.
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+/// Builds a geocoder from a spec such as "nominatim", "nominatim=URL",
+/// "photon", "photon=URL" or "file=PATH".
+pub fn parse_geocoder(spec: &str) -> Result<Box<dyn Geocoder>, Box<dyn std::error::Error>> {
+    let (kind, target) = match spec.split_once('=') {
+        Some((kind, target)) => (kind.trim(), Some(target.trim())),
+        None => (spec.trim(), None),
+    };
+    match (kind.to_lowercase().as_str(), target) {
+        ("nominatim" | "osm", None) => Ok(Box::new(Nominatim::new(Nominatim::DEFAULT_ENDPOINT))),
+        ("nominatim" | "osm", Some(url)) => Ok(Box::new(Nominatim::new(url))),
+        ("photon", None) => Ok(Box::new(Photon::new(Photon::DEFAULT_ENDPOINT))),
+        ("photon", Some(url)) => Ok(Box::new(Photon::new(url))),
+        ("file", Some(path)) => Ok(Box::new(StaticFile::open(path)?)),
+        ("file", None) => Err("The file geocoder needs a path, as in file=places.csv".into()),
+        _ => Err(format!("Unknown geocoder: {}", spec).into()),
+    }
+}
+
+/// Ensures an endpoint URL ends in a slash, so paths can be appended to it.
+fn base_url(endpoint: &str) -> String {
+    format!("{}/", endpoint.trim_end_matches('/'))
+}
+
+/// The Nominatim API, as run by OpenStreetMap or self-hosted.
+pub struct Nominatim {
+    endpoint: String,
+    osm: Openstreetmap,
+}
+
+impl Nominatim {
+    pub const DEFAULT_ENDPOINT: &'static str = "https://nominatim.openstreetmap.org/";
+
+    pub fn new(endpoint: &str) -> Self {
+        let endpoint = base_url(endpoint);
+        Nominatim {
+            osm: Openstreetmap::new_with_endpoint(endpoint.clone()),
+            endpoint,
+        }
+    }
+}
+
+impl Geocoder for Nominatim {
+    fn forward(&self, query: &str) -> Result<Option<(f64, f64)>, Box<dyn std::error::Error>> {
+        let res: Vec<Point<f64>> = self.osm.forward(query)?;
+        Ok(res.first().map(|point| (point.y(), point.x())))
+    }
+
+    fn source(&self) -> String {
+        format!("nominatim {}", self.endpoint)
+    }
+}
+
+/// The Photon geocoding API, as run by Komoot or self-hosted.
+pub struct Photon {
+    endpoint: String,
+    client: reqwest::blocking::Client,
+}
+
+#[derive(Deserialize)]
+struct PhotonResponse {
+    features: Vec<PhotonFeature>,
+}
+
+#[derive(Deserialize)]
+struct PhotonFeature {
+    geometry: PhotonGeometry,
+}
+
+#[derive(Deserialize)]
+struct PhotonGeometry {
+    /// GeoJSON order: longitude, then latitude
+    coordinates: (f64, f64),
+}
+
+impl Photon {
+    pub const DEFAULT_ENDPOINT: &'static str = "https://photon.komoot.io/";
+
+    pub fn new(endpoint: &str) -> Self {
+        Photon {
+            endpoint: base_url(endpoint),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl Geocoder for Photon {
+    fn forward(&self, query: &str) -> Result<Option<(f64, f64)>, Box<dyn std::error::Error>> {
+        let res: PhotonResponse = self.client
+            .get(format!("{}api", self.endpoint))
+            .query(&[("q", query), ("limit", "1")])
+            .header(reqwest::header::USER_AGENT, "tdate")
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(res.features.first().map(|feature| {
+            let (lon, lat) = feature.geometry.coordinates;
+            (lat, lon)
+        }))
+    }
+
+    fn source(&self) -> String {
+        format!("photon {}", self.endpoint)
+    }
+}
+
+/// A fixed list of places read from a JSON or CSV file.
+///
+/// JSON files hold an array of `{"name": ..., "latitude": ..., "longitude": ...}`
+/// objects; CSV files need a header row with `name`, `latitude` and
+/// `longitude` columns. Names are matched ignoring case and spacing.
+pub struct StaticFile {
+    path: PathBuf,
+    places: Vec<StaticPlace>,
+}
+
+#[derive(Deserialize)]
+struct StaticPlace {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+impl StaticFile {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref().to_path_buf();
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let places = if is_json {
+            serde_json::from_str(&contents)?
+        } else {
+            Self::parse_csv(&contents)?
+        };
+        Ok(StaticFile { path, places })
+    }
+
+    fn parse_csv(contents: &str) -> Result<Vec<StaticPlace>, Box<dyn std::error::Error>> {
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+        let header: Vec<String> = lines.next()
+            .ok_or("The places file is empty")?
+            .split(',')
+            .map(|column| column.trim().to_lowercase())
+            .collect();
+        let column = |name: &str| header.iter()
+            .position(|column| column == name)
+            .ok_or_else(|| format!("The places file has no {} column", name));
+        let (name, lat, lon) = (column("name")?, column("latitude")?, column("longitude")?);
+
+        lines.map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |i: usize| fields.get(i).copied().ok_or_else(|| format!("Missing field in: {}", line));
+            Ok(StaticPlace {
+                name: field(name)?.to_string(),
+                latitude: field(lat)?.parse().map_err(|_| format!("Invalid latitude in: {}", line))?,
+                longitude: field(lon)?.parse().map_err(|_| format!("Invalid longitude in: {}", line))?,
+            })
+        }).collect()
+    }
+}
+
+impl Geocoder for StaticFile {
+    fn forward(&self, query: &str) -> Result<Option<(f64, f64)>, Box<dyn std::error::Error>> {
+        let query = LocationCache::normalize(query);
+        Ok(self.places.iter()
+            .find(|place| LocationCache::normalize(&place.name) == query)
+            .map(|place| (place.latitude, place.longitude)))
+    }
+
+    fn source(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    fn is_remote(&self) -> bool {
+        false
+    }
+}
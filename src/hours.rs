@@ -0,0 +1,178 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::fmt;
+
+use crate::{to_ascii, Language, Location, Style, ThelemicDate};
+
+/// The rulers in Chaldean order, slowest to fastest (Saturn, Jupiter, Mars,
+/// Sun, Venus, Mercury, Moon), as indices into `ThelemicDate::DAYS_OF_WEEK`.
+const CHALDEAN_ORDER: [usize; 7] = [5, 3, 1, 6, 4, 2, 0];
+
+/// Glyphs of the rulers, indexed like `ThelemicDate::DAYS_OF_WEEK`.
+pub(crate) const RULER_GLYPHS: [&str; 7] = ["☽", "♂", "☿", "♃", "♀", "♄", "☉"];
+
+/// One of the 24 unequal hours of a planetary day: twelve from sunrise to
+/// sunset, twelve from sunset to the next sunrise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PlanetaryHour {
+    /// 1 to 24, counted from sunrise
+    pub number: usize,
+    /// Ruler of the hour, as an index into `ThelemicDate::DAYS_OF_WEEK`
+    pub ruler: usize,
+    /// Ruler of the planetary day the hour belongs to, which is also its
+    /// weekday, as an index into `ThelemicDate::DAYS_OF_WEEK`
+    pub day: usize,
+    pub start: DateTime<Tz>,
+    pub end: DateTime<Tz>,
+    /// Language the hour is named in when displayed
+    #[serde(skip)]
+    pub language: Language,
+    /// Style the hour is displayed in
+    #[serde(skip)]
+    pub style: Style,
+}
+
+impl PlanetaryHour {
+    /// Latin genitive of the ruler, as in "hora Saturni".
+    pub fn ruler_name(&self) -> &'static str {
+        ThelemicDate::DAYS_OF_WEEK[self.ruler]
+    }
+
+    pub fn ruler_glyph(&self) -> &'static str {
+        RULER_GLYPHS[self.ruler]
+    }
+
+    /// Whether the hour falls between sunset and sunrise.
+    pub fn is_night(&self) -> bool {
+        self.number > 12
+    }
+
+    /// The planetary day the hour belongs to, e.g. "dies Saturnii", "♄" or
+    /// "Tag des Saturn".
+    pub fn weekday(&self) -> String {
+        match self.style {
+            Style::Text => self.language.weekday(self.day).to_string(),
+            Style::Glyph => RULER_GLYPHS[self.day].to_string(),
+            Style::Ascii => to_ascii(self.language.weekday(self.day)),
+        }
+    }
+}
+
+impl fmt::Display for PlanetaryHour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Style::Text => write!(f, "{} {}", self.ruler_glyph(), self.language.hour(self.ruler)),
+            Style::Glyph => write!(f, "hora {}", self.ruler_glyph()),
+            Style::Ascii => write!(f, "{}", to_ascii(self.language.hour(self.ruler))),
+        }
+    }
+}
+
+impl ThelemicDate {
+    /// Computes the 24 planetary hours of the planetary day beginning at
+    /// sunrise on `date` at a location, which must have coordinates.
+    pub fn planetary_hours(&self, date: NaiveDate, location: &Location)
+        -> Result<Vec<PlanetaryHour>, Box<dyn std::error::Error>> {
+        let resolved = self.resolve(location)?;
+        let (latitude, longitude) = resolved.coordinates()
+            .ok_or("Planetary hours need coordinates; give a place or --coords rather than a timezone")?;
+        Ok(Self::planetary_hours_at(date, latitude, longitude, &resolved.timezone)?
+            .into_iter()
+            .map(|hour| PlanetaryHour { language: self.language, style: self.style, ..hour })
+            .collect())
+    }
+
+    /// Computes the 24 planetary hours of the planetary day beginning at
+    /// sunrise on `date`, at a latitude and longitude in degrees. The hours
+    /// are displayed in English and the text style.
+    pub fn planetary_hours_at(date: NaiveDate, latitude: f64, longitude: f64, tz: &Tz)
+        -> Result<Vec<PlanetaryHour>, Box<dyn std::error::Error>> {
+        let next_date = date.succ_opt().ok_or("Date out of range")?;
+        let today = Self::resh_at(date, latitude, longitude, tz)?;
+        let tomorrow = Self::resh_at(next_date, latitude, longitude, tz)?;
+        let (Some(sunrise), Some(sunset), Some(next_sunrise)) = (today.sunrise, today.sunset, tomorrow.sunrise) else {
+            return Err(format!("The Sun does not both rise and set on {}; planetary hours are undefined", date).into());
+        };
+
+        let day = Self::weekday_to_index(date.weekday());
+        let first = CHALDEAN_ORDER.iter().position(|&ruler| ruler == day).expect("Every weekday has a ruler");
+        let day_hour = (sunset - sunrise) / 12;
+        let night_hour = (next_sunrise - sunset) / 12;
+
+        Ok((0..24)
+            .map(|i| {
+                let (start, length, n) = if i < 12 { (sunrise, day_hour, i) } else { (sunset, night_hour, i - 12) };
+                PlanetaryHour {
+                    number: i + 1,
+                    ruler: CHALDEAN_ORDER[(first + i) % 7],
+                    day,
+                    start: start + length * n as i32,
+                    // The last hour of each half runs exactly to sunset or sunrise
+                    end: match i {
+                        11 => sunset,
+                        23 => next_sunrise,
+                        _ => start + length * (n as i32 + 1),
+                    },
+                    language: Language::default(),
+                    style: Style::default(),
+                }
+            })
+            .collect())
+    }
+
+    /// Finds the planetary hour an instant falls in, at a latitude and
+    /// longitude in degrees.
+    pub fn planetary_hour_at(dt: &DateTime<Tz>, latitude: f64, longitude: f64)
+        -> Result<PlanetaryHour, Box<dyn std::error::Error>> {
+        let tz = dt.timezone();
+        // Before sunrise the planetary day is still yesterday's
+        let date = dt.date_naive();
+        for date in [date, date - Duration::days(1)] {
+            let hours = Self::planetary_hours_at(date, latitude, longitude, &tz)?;
+            if let Some(hour) = hours.into_iter().find(|hour| hour.start <= *dt && *dt < hour.end) {
+                return Ok(hour);
+            }
+        }
+        Err("No planetary hour found".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::London;
+
+    #[test]
+    fn names_the_hour_in_the_language_and_style() {
+        let start = London.with_ymd_and_hms(2025, 7, 23, 5, 11, 0).unwrap();
+        let mut hour = PlanetaryHour {
+            number: 3,
+            ruler: 5,
+            day: 2,
+            start,
+            end: start + Duration::minutes(79),
+            language: Language::English,
+            style: Style::Text,
+        };
+        assert_eq!(hour.to_string(), "♄ hora Saturnii");
+        assert_eq!(hour.weekday(), "dies Mercurii");
+        hour.style = Style::Glyph;
+        assert_eq!(hour.to_string(), "hora ♄");
+        assert_eq!(hour.weekday(), "☿");
+        hour.language = Language::German;
+        hour.style = Style::Text;
+        assert_eq!(hour.to_string(), "♄ Stunde des Saturn");
+
+        hour.style = Style::Ascii;
+        for language in [Language::English, Language::Spanish, Language::Portuguese, Language::German, Language::French] {
+            hour.language = language;
+            for ruler in 0..7 {
+                hour.ruler = ruler;
+                assert!(hour.to_string().is_ascii(), "{}", hour);
+                assert!(hour.weekday().is_ascii(), "{}", hour.weekday());
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use astro::{ecliptic, nutation, time};
+use serde::Serialize;
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{to_ascii, Language, Position, Style, ThelemicDate, Zodiac};
+
+/// Ways of dividing the sky into the twelve houses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum HouseSystem {
+    Placidus,
+    WholeSign,
+    Equal,
+    Koch,
+}
+
+impl HouseSystem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HouseSystem::Placidus => "Placidus",
+            HouseSystem::WholeSign => "Whole Sign",
+            HouseSystem::Equal => "Equal",
+            HouseSystem::Koch => "Koch",
+        }
+    }
+}
+
+impl FromStr for HouseSystem {
+    type Err = Box<dyn std::error::Error>;
+
+    /// Parses a system name such as "placidus", "whole-sign" or "equal".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "placidus" | "p" => Ok(HouseSystem::Placidus),
+            "wholesign" | "whole" | "w" => Ok(HouseSystem::WholeSign),
+            "equal" | "e" => Ok(HouseSystem::Equal),
+            "koch" | "k" => Ok(HouseSystem::Koch),
+            _ => Err(format!(
+                "Unknown house system: {} (expected placidus, whole-sign, equal or koch)", s
+            ).into()),
+        }
+    }
+}
+
+/// The angles and house cusps of a chart cast for a place and instant.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Houses {
+    pub system: HouseSystem,
+    /// Local apparent sidereal time, in hours within [0, 24)
+    pub local_sidereal_time: f64,
+    pub ascendant: Position,
+    pub midheaven: Position,
+    /// Cusps of the first through twelfth houses
+    pub cusps: [Position; 12],
+    /// Language the labels and system are named in when displayed
+    #[serde(skip)]
+    pub language: Language,
+    /// Style the line of cusps is displayed in
+    #[serde(skip)]
+    pub style: Style,
+}
+
+impl Houses {
+    /// Local sidereal time as "HH:MM:SS".
+    pub fn sidereal_time_hms(&self) -> String {
+        let seconds = (self.local_sidereal_time * 3600.0).floor() as u32 % 86400;
+        format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+impl fmt::Display for Houses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [asc, mc, lst] = self.language.angle_labels();
+        let system = self.language.house_system(self.system);
+        write!(
+            f,
+            "{} {} : {} {} : {} {} : {}",
+            asc,
+            self.ascendant,
+            mc,
+            self.midheaven,
+            lst,
+            self.sidereal_time_hms(),
+            if self.style == Style::Ascii { to_ascii(system) } else { system.to_string() }
+        )?;
+        for (i, cusp) in self.cusps.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { "" } else { "," }, cusp)?;
+        }
+        Ok(())
+    }
+}
+
+impl ThelemicDate {
+    /// Casts the angles and house cusps for Julian Day `jd` (UT) as seen from
+    /// a latitude and longitude in degrees, east positive. Placidus and Koch
+    /// are undefined within the polar circles, where some degrees of the
+    /// ecliptic never rise; an error is returned there.
+    pub fn houses(&self, jd: f64, latitude: f64, longitude: f64, system: HouseSystem)
+        -> Result<Houses, Box<dyn std::error::Error>> {
+        let (nut_in_long, nut_in_oblq) = nutation::nutation(jd);
+        let oblq = ecliptic::mn_oblq_IAU(jd) + nut_in_oblq;
+        let greenwich = time::apprnt_sidr(time::mn_sidr(jd), nut_in_long, oblq);
+        // Right ascension of the meridian
+        let ramc = (greenwich + longitude.to_radians()).rem_euclid(2.0 * PI);
+        let lat = latitude.to_radians();
+
+        // Everything below is measured in the selected zodiac, so that whole
+        // sign houses start at the sidereal sign when one is in use. The
+        // angles are counted from the true equinox and the ayanamsa from the
+        // mean one, so a sidereal zodiac takes off the nutation too
+        let offset = match self.zodiac {
+            Zodiac::Tropical => 0.0,
+            Zodiac::Sidereal(_) => self.zodiac.offset(jd).to_radians() + nut_in_long,
+        };
+        let asc = ascendant(ramc, oblq, lat) - offset;
+        let mc = long_frm_ra(ramc, oblq) - offset;
+
+        let cusps: [f64; 12] = match system {
+            HouseSystem::Equal => std::array::from_fn(|i| asc + i as f64 * PI / 6.0),
+            HouseSystem::WholeSign => {
+                let first = (asc.rem_euclid(2.0 * PI) / (PI / 6.0)).floor();
+                // Nudged a hair past each sign boundary so that rounding
+                // cannot place a cusp at 29º of the sign before
+                std::array::from_fn(|i| (first + i as f64) * PI / 6.0 + 1e-9)
+            }
+            HouseSystem::Placidus => {
+                let [c11, c12, c2, c3] = placidus(ramc, oblq, lat)
+                    .ok_or("Placidus houses are undefined within the polar circles")?
+                    .map(|cusp| cusp - offset);
+                quadrants(asc, mc, c11, c12, c2, c3)
+            }
+            HouseSystem::Koch => {
+                let [c11, c12, c2, c3] = koch(ramc, oblq, lat, mc + offset)
+                    .ok_or("Koch houses are undefined within the polar circles")?
+                    .map(|cusp| cusp - offset);
+                quadrants(asc, mc, c11, c12, c2, c3)
+            }
+        };
+
+        Ok(Houses {
+            system,
+            local_sidereal_time: ramc.to_degrees() / 15.0,
+            ascendant: self.position(asc),
+            midheaven: self.position(mc),
+            cusps: cusps.map(|cusp| self.position(cusp)),
+            language: self.language,
+            style: self.style,
+        })
+    }
+}
+
+/// Completes the twelve cusps of a quadrant system from the angles and the
+/// intermediate cusps of the eastern half; the rest lie opposite.
+fn quadrants(asc: f64, mc: f64, c11: f64, c12: f64, c2: f64, c3: f64) -> [f64; 12] {
+    let east = [asc, c2, c3, mc + PI, c11 + PI, c12 + PI];
+    std::array::from_fn(|i| if i < 6 { east[i] } else { east[i - 6] + PI })
+}
+
+/// Ecliptic longitude rising in the east for a right ascension of the
+/// meridian, obliquity and latitude, all in radians.
+fn ascendant(ramc: f64, oblq: f64, lat: f64) -> f64 {
+    f64::atan2(ramc.cos(), -(ramc.sin() * oblq.cos() + lat.tan() * oblq.sin()))
+}
+
+/// Ecliptic longitude of the point with right ascension `ra`.
+fn long_frm_ra(ra: f64, oblq: f64) -> f64 {
+    f64::atan2(ra.sin(), ra.cos() * oblq.cos())
+}
+
+/// Ascensional difference of an ecliptic longitude at a latitude, or `None`
+/// where the point is circumpolar.
+fn ascensional_diff(long: f64, oblq: f64, lat: f64) -> Option<f64> {
+    let decl = (oblq.sin() * long.sin()).asin();
+    let x = lat.tan() * decl.tan();
+    (x.abs() <= 1.0).then(|| x.asin())
+}
+
+/// Placidus cusps 11, 12, 2 and 3, each the point that has covered its share
+/// of its own diurnal or nocturnal semi-arc.
+fn placidus(ramc: f64, oblq: f64, lat: f64) -> Option<[f64; 4]> {
+    // (fraction of the semi-arc, whether it is measured below the horizon)
+    let cusp = |fraction: f64, below: bool| -> Option<f64> {
+        let ra_of = |ad: f64| if below {
+            ramc + PI - fraction * (PI / 2.0 - ad)
+        } else {
+            ramc + fraction * (PI / 2.0 + ad)
+        };
+        let mut long = long_frm_ra(ra_of(0.0), oblq);
+        for _ in 0..50 {
+            let next = long_frm_ra(ra_of(ascensional_diff(long, oblq, lat)?), oblq);
+            let change = (next - long + PI).rem_euclid(2.0 * PI) - PI;
+            long = next;
+            if change.abs() < 1e-10 {
+                break;
+            }
+        }
+        Some(long)
+    };
+    Some([cusp(1.0 / 3.0, false)?, cusp(2.0 / 3.0, false)?, cusp(2.0 / 3.0, true)?, cusp(1.0 / 3.0, true)?])
+}
+
+/// Koch cusps 11, 12, 2 and 3, the degrees rising as the Midheaven's degree
+/// covers each third of its diurnal semi-arc.
+fn koch(ramc: f64, oblq: f64, lat: f64, mc: f64) -> Option<[f64; 4]> {
+    let ad = ascensional_diff(mc, oblq, lat)?;
+    let third = (PI / 2.0 + ad) / 3.0;
+    // The MC degree's own rising is `ramc - 3 * third`; cusp 1 is at `ramc`
+    let cusp = |n: f64| ascendant(ramc + (n - 3.0) * third, oblq, lat);
+    Some([cusp(1.0), cusp(2.0), cusp(4.0), cusp(5.0)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ayanamsa;
+
+    /// 2025 July 23, 11:00 UT, in London.
+    const JD: f64 = 2_460_879.958_333_333;
+    const LATITUDE: f64 = 51.5074;
+    const LONGITUDE: f64 = -0.1278;
+
+    fn assert_near(found: &Position, expected: f64) {
+        let error = (found.longitude - expected + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(error.abs() < 0.05, "found {:.3}, expected {:.3}", found.longitude, expected);
+    }
+
+    #[test]
+    fn casts_a_placidus_chart() {
+        let houses = ThelemicDate::new().with_cache(None)
+            .houses(JD, LATITUDE, LONGITUDE, HouseSystem::Placidus)
+            .unwrap();
+        assert!((houses.local_sidereal_time - 7.0873).abs() < 0.001, "{}", houses.local_sidereal_time);
+        assert_eq!(houses.sidereal_time_hms(), "07:05:14");
+        assert_near(&houses.ascendant, 191.50);
+        assert_near(&houses.midheaven, 105.03);
+        for (cusp, expected) in [(1, 191.50), (2, 216.70), (3, 248.09), (10, 105.03), (11, 140.33), (12, 169.07)] {
+            assert_near(&houses.cusps[cusp - 1], expected);
+            assert_near(&houses.cusps[(cusp + 5) % 12], (expected + 180.0) % 360.0);
+        }
+        assert_eq!(houses.cusps[0].sign, "Libra");
+        assert_eq!(houses.cusps[0].degree, 11);
+    }
+
+    #[test]
+    fn divides_equal_and_whole_sign_houses() {
+        let date_data = ThelemicDate::new().with_cache(None);
+        let equal = date_data.houses(JD, LATITUDE, LONGITUDE, HouseSystem::Equal).unwrap();
+        for (i, cusp) in equal.cusps.iter().enumerate() {
+            assert_near(cusp, 191.50 + 30.0 * i as f64);
+        }
+        let whole = date_data.houses(JD, LATITUDE, LONGITUDE, HouseSystem::WholeSign).unwrap();
+        for (i, cusp) in whole.cusps.iter().enumerate() {
+            assert_eq!(cusp.sign, ThelemicDate::SIGNS[(6 + i) % 12].0);
+            assert_eq!(cusp.degree, 0);
+        }
+        assert_near(&whole.ascendant, 191.50);
+    }
+
+    #[test]
+    fn counts_sidereal_houses_from_the_sidereal_zodiac() {
+        let date_data = ThelemicDate::new().with_cache(None)
+            .with_zodiac(Zodiac::Sidereal(Ayanamsa::Lahiri));
+        let houses = date_data.houses(JD, LATITUDE, LONGITUDE, HouseSystem::WholeSign).unwrap();
+        // The Ascendant falls back about 24º into Virgo, whose house is the first
+        assert_eq!(houses.ascendant.sign, "Virgo");
+        assert_eq!(houses.cusps[0].sign, "Virgo");
+        assert_eq!(houses.cusps[0].degree, 0);
+    }
+
+    #[test]
+    fn refuses_quadrant_houses_within_the_polar_circles() {
+        // With the Midheaven near the summer solstice, its degree never sets
+        let date_data = ThelemicDate::new().with_cache(None);
+        assert!(date_data.houses(JD, 70.0, LONGITUDE, HouseSystem::Placidus).is_err());
+        assert!(date_data.houses(JD, 70.0, LONGITUDE, HouseSystem::Koch).is_err());
+        assert!(date_data.houses(JD, 70.0, LONGITUDE, HouseSystem::Equal).is_ok());
+    }
+}
@@ -0,0 +1,374 @@
+use chrono::{DateTime, Datelike, Duration, DurationRound, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use std::io::{self, Write};
+
+use crate::{to_ascii, Anno, Luminary, ResolvedLocation, Style, ThelemicDate, Zodiac};
+
+/// When a calendar event happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTime {
+    /// A whole local day, for feasts kept on a fixed date
+    Date(NaiveDate),
+    /// An instant, given to the minute
+    Time(DateTime<Tz>),
+}
+
+/// An entry of an iCalendar file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    /// The Thelemic date of the event
+    pub description: String,
+    pub time: EventTime,
+}
+
+impl ThelemicDate {
+    /// Collects the feasts, Sun ingresses and new and full moons between two
+    /// instants as calendar events, each described by its Thelemic date at
+    /// the location. Ingresses into the cardinal signs are left out in the
+    /// tropical zodiac, where the equinox and solstice feasts stand for them.
+    pub fn calendar_events(&self, from: &DateTime<Tz>, to: &DateTime<Tz>, location: &ResolvedLocation)
+        -> Result<Vec<CalendarEvent>, Box<dyn std::error::Error>> {
+        let tz = location.timezone;
+        let describe = |dt: &DateTime<Tz>| -> Result<String, Box<dyn std::error::Error>> {
+            Ok(self.at_location(dt, location)?.to_string())
+        };
+        let mut events = Vec::new();
+
+        // Every feast falls between March and December of the year its Anno begins
+        for year in from.with_timezone(&tz).year()..=to.with_timezone(&tz).year() {
+            for feast in Self::feasts(Anno::new(year - 1904)?, &tz)? {
+                let moment = feast.moment(&tz)?;
+                if moment < *from || moment >= *to {
+                    continue;
+                }
+                events.push(CalendarEvent {
+                    uid: uid(&moment, feast.observance.name()),
+                    summary: feast.observance.name().to_string(),
+                    description: describe(&moment)?,
+                    time: match feast.time {
+                        Some(time) => EventTime::Time(time),
+                        None => EventTime::Date(feast.date),
+                    },
+                });
+            }
+        }
+
+        for ingress in self.ingresses(&[Luminary::Sun], from, to)? {
+            if self.zodiac == Zodiac::Tropical && ingress.sign % 3 == 0 {
+                continue;
+            }
+            let time = next_minute(&ingress.time)?;
+            events.push(CalendarEvent {
+                uid: uid(&time, &format!("{} enters {}", ingress.body.name(), ingress.sign_name())),
+                summary: ingress.event(),
+                description: describe(&time)?,
+                time: EventTime::Time(time),
+            });
+        }
+
+        for syzygy in self.syzygies(from, to)? {
+            let time = next_minute(&syzygy.time)?;
+            events.push(CalendarEvent {
+                uid: uid(&time, syzygy.phase.name()),
+                summary: match self.style {
+                    Style::Ascii => to_ascii(self.language.phase(syzygy.phase)),
+                    Style::Text | Style::Glyph => self.language.phase(syzygy.phase).to_string(),
+                },
+                description: describe(&time)?,
+                time: EventTime::Time(time),
+            });
+        }
+
+        events.sort_by_key(|event| match event.time {
+            EventTime::Date(date) => (date, None),
+            EventTime::Time(time) => (time.date_naive(), Some(time)),
+        });
+        Ok(events)
+    }
+}
+
+/// Writes events as an iCalendar (RFC 5545) file, with their times given in
+/// the timezone `tz` and a VTIMEZONE describing it over the events' span.
+pub fn write_ics(events: &[CalendarEvent], tz: &Tz, out: &mut impl Write) -> io::Result<()> {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//tdate//tdate {}//EN", env!("CARGO_PKG_VERSION")),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Thelemic calendar".to_string(),
+        format!("X-WR-TIMEZONE:{}", tz.name()),
+    ];
+
+    let instants: Vec<DateTime<Utc>> = events.iter()
+        .map(|event| match event.time {
+            EventTime::Time(time) => time.with_timezone(&Utc),
+            EventTime::Date(date) => date.and_time(chrono::NaiveTime::MIN).and_utc(),
+        })
+        .collect();
+    if let (Some(first), Some(last)) = (instants.iter().min(), instants.iter().max()) {
+        lines.extend(vtimezone(tz, *first - Duration::days(1), *last + Duration::days(1)));
+    }
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        match event.time {
+            EventTime::Date(date) => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+                if let Some(next) = date.succ_opt() {
+                    lines.push(format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+                }
+            }
+            EventTime::Time(time) => {
+                lines.push(format!("DTSTART;TZID={}:{}", tz.name(), time.with_timezone(tz).format("%Y%m%dT%H%M%S")));
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape(&event.description)));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    for line in lines {
+        write!(out, "{}\r\n", fold(&line))?;
+    }
+    Ok(())
+}
+
+/// The VTIMEZONE component for `tz`, giving the offset in force at `from`
+/// and every change of offset up to `to`.
+fn vtimezone(tz: &Tz, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<String> {
+    let offset_at = |t: DateTime<Utc>| tz.offset_from_utc_datetime(&t.naive_utc());
+    let seconds = |t: DateTime<Utc>| offset_at(t).fix().local_minus_utc();
+
+    // When each observance begins, with the offset in force before it
+    let mut observances = vec![(from, seconds(from))];
+    let mut day = from;
+    while day < to {
+        let next = day + Duration::days(1);
+        if seconds(next) != seconds(day) {
+            // Narrow the change down to the second
+            let (mut before, mut after) = (day, next);
+            while after - before > Duration::seconds(1) {
+                let mid = before + (after - before) / 2;
+                if seconds(mid) == seconds(before) { before = mid } else { after = mid }
+            }
+            observances.push((after, seconds(before)));
+        }
+        day = next;
+    }
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    for (start, offset_from) in observances {
+        let offset = offset_at(start);
+        let kind = if offset.dst_offset().is_zero() { "STANDARD" } else { "DAYLIGHT" };
+        // Onsets are written in the local time in force before them
+        let onset: NaiveDateTime = start.naive_utc() + Duration::seconds(offset_from as i64);
+        lines.push(format!("BEGIN:{}", kind));
+        lines.push(format!("DTSTART:{}", onset.format("%Y%m%dT%H%M%S")));
+        lines.push(format!("TZOFFSETFROM:{}", utc_offset(offset_from)));
+        lines.push(format!("TZOFFSETTO:{}", utc_offset(offset.fix().local_minus_utc())));
+        lines.push(format!("TZNAME:{}", offset.abbreviation()));
+        lines.push(format!("END:{}", kind));
+    }
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+/// The first whole minute after an instant, so that an event given to the
+/// minute is never shown before it happened.
+fn next_minute(time: &DateTime<Tz>) -> Result<DateTime<Tz>, Box<dyn std::error::Error>> {
+    Ok(time.duration_trunc(Duration::minutes(1))? + Duration::minutes(1))
+}
+
+/// A unique identifier for an event, from its time and summary.
+fn uid(time: &DateTime<Tz>, summary: &str) -> String {
+    let slug: String = summary.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == ' ')
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    format!("{}-{}@tdate", time.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ"), slug)
+}
+
+/// Formats an offset from UTC in seconds as iCalendar's `+HHMM`.
+fn utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// Escapes text for an iCalendar property value.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to at most 75 octets per line, without splitting a
+/// character.
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        // Continuation lines begin with a space, which counts towards their length
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::{America::St_Johns, Asia::Kolkata, Australia::Adelaide, Europe::London};
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn folds_lines_at_75_octets() {
+        assert_eq!(fold("SUMMARY:short"), "SUMMARY:short");
+        let line = format!("DESCRIPTION:{}", "x".repeat(150));
+        let folded = fold(&line);
+        let parts: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|part| part.len() <= 75));
+        assert!(parts[1..].iter().all(|part| part.starts_with(' ')));
+        assert_eq!(parts.concat().replace(' ', ""), line);
+    }
+
+    #[test]
+    fn folds_between_characters() {
+        // Three-octet glyphs, which cannot fall evenly on the 75th octet
+        let line = format!("SUMMARY:{}", "☉♌".repeat(30));
+        let folded = fold(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= 75, "{} octets", part.len());
+        }
+        let unfolded: String = folded.split("\r\n").enumerate()
+            .map(|(i, part)| if i == 0 { part } else { &part[1..] })
+            .collect();
+        assert_eq!(unfolded, line);
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape("☉ in 0º Leo : Anno Vxi"), "☉ in 0º Leo : Anno Vxi");
+        assert_eq!(escape("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn writes_utc_offsets() {
+        assert_eq!(utc_offset(0), "+0000");
+        assert_eq!(utc_offset(3600), "+0100");
+        assert_eq!(utc_offset(19_800), "+0530");
+        assert_eq!(utc_offset(-12_600), "-0330");
+        assert_eq!(utc_offset(-36_000), "-1000");
+    }
+
+    #[test]
+    fn describes_daylight_saving_changes() {
+        let lines = vtimezone(&London, utc(2025, 1, 1), utc(2026, 1, 1));
+        assert_eq!(lines, [
+            "BEGIN:VTIMEZONE", "TZID:Europe/London",
+            "BEGIN:STANDARD", "DTSTART:20250101T000000", "TZOFFSETFROM:+0000", "TZOFFSETTO:+0000", "TZNAME:GMT", "END:STANDARD",
+            "BEGIN:DAYLIGHT", "DTSTART:20250330T010000", "TZOFFSETFROM:+0000", "TZOFFSETTO:+0100", "TZNAME:BST", "END:DAYLIGHT",
+            "BEGIN:STANDARD", "DTSTART:20251026T020000", "TZOFFSETFROM:+0100", "TZOFFSETTO:+0000", "TZNAME:GMT", "END:STANDARD",
+            "END:VTIMEZONE",
+        ]);
+    }
+
+    #[test]
+    fn describes_half_hour_offsets() {
+        let lines = vtimezone(&St_Johns, utc(2025, 1, 1), utc(2025, 12, 31));
+        assert!(lines.contains(&"DTSTART:20250309T020000".to_string()), "{:?}", lines);
+        assert!(lines.contains(&"TZOFFSETTO:-0230".to_string()));
+        assert!(lines.contains(&"TZOFFSETFROM:-0230".to_string()));
+        assert!(lines.contains(&"TZOFFSETTO:-0330".to_string()));
+
+        // Southern hemisphere: daylight saving ends in April and starts in October
+        let lines = vtimezone(&Adelaide, utc(2025, 1, 1), utc(2025, 12, 31));
+        assert!(lines.contains(&"DTSTART:20250406T030000".to_string()), "{:?}", lines);
+        assert!(lines.contains(&"DTSTART:20251005T020000".to_string()), "{:?}", lines);
+        assert!(lines.contains(&"TZOFFSETTO:+1030".to_string()));
+
+        let lines = vtimezone(&Kolkata, utc(2025, 1, 1), utc(2025, 12, 31));
+        assert_eq!(lines.iter().filter(|line| line.starts_with("BEGIN:")).count(), 2);
+        assert!(lines.contains(&"TZOFFSETTO:+0530".to_string()));
+    }
+
+    #[test]
+    fn collects_an_annos_events() {
+        let location = ResolvedLocation { latitude: Some(51.5074), longitude: Some(-0.1278), timezone: London };
+        let date_data = ThelemicDate::new().with_cache(None);
+        let from = ThelemicDate::vernal_equinox(2025, &London).unwrap();
+        let to = ThelemicDate::vernal_equinox(2026, &London).unwrap();
+        let events = date_data.calendar_events(&from, &to, &location).unwrap();
+
+        let feasts = events.iter()
+            .filter(|event| ThelemicDate::OBSERVANCES.iter().any(|observance| observance.name() == event.summary))
+            .count();
+        let ingresses: Vec<_> = events.iter().filter(|event| event.summary.starts_with('☉')).collect();
+        let syzygies = events.iter().filter(|event| event.summary.ends_with("Moon")).count();
+        // The Supreme Ritual falls after the equinox in 2025 and before it in 2026
+        assert_eq!(feasts, 12);
+        // The ingresses into the cardinal signs are left to the feasts
+        assert_eq!(ingresses.len(), 8);
+        assert!(ingresses.iter().all(|event| !event.summary.contains("Aries") && !event.summary.contains("Cancer")));
+        assert!((24..=26).contains(&syzygies), "{}", syzygies);
+        assert_eq!(feasts + ingresses.len() + syzygies, events.len());
+
+        let day = |event: &CalendarEvent| match event.time {
+            EventTime::Date(date) => date,
+            EventTime::Time(time) => time.date_naive(),
+        };
+        assert!(events.windows(2).all(|pair| day(&pair[0]) <= day(&pair[1])));
+        for event in &events {
+            if let EventTime::Time(time) = event.time {
+                assert_eq!(time.timestamp() % 60, 0, "{}", event.summary);
+                assert!(time > from && time <= to + Duration::minutes(1), "{}", event.summary);
+            }
+            assert!(event.description.contains("Anno V"), "{}", event.description);
+            assert!(event.uid.ends_with("@tdate"));
+        }
+    }
+
+    #[test]
+    fn writes_a_well_formed_calendar() {
+        let event = CalendarEvent {
+            uid: "20250722T132900Z-sun-enters-leo@tdate".to_string(),
+            summary: "☉ enters Leo".to_string(),
+            description: "☉ in 0º Leo : ☽ in 13º Cancer : dies Mercurii : Anno Vxi æræ legis, with a description long enough to fold".to_string(),
+            time: EventTime::Time(London.with_ymd_and_hms(2025, 7, 22, 14, 30, 0).unwrap()),
+        };
+        let mut out = Vec::new();
+        write_ics(&[event], &London, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+        assert!(!text.replace("\r\n", "").contains('\n'));
+        assert!(text.split("\r\n").all(|line| line.len() <= 75));
+        assert!(text.contains("DTSTART;TZID=Europe/London:20250722T143000\r\n"));
+        assert!(text.replace("\r\n ", "").contains("DESCRIPTION:☉ in 0º Leo : ☽ in 13º Cancer : dies Mercurii : Anno Vxi æræ legis\\,"));
+        for component in ["VCALENDAR", "VTIMEZONE", "VEVENT"] {
+            assert_eq!(
+                text.matches(&format!("BEGIN:{}", component)).count(),
+                text.matches(&format!("END:{}", component)).count()
+            );
+        }
+    }
+}
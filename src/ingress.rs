@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{to_ascii, Language, Style, ThelemicDate};
+
+/// The bodies whose sign ingresses can be found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Luminary {
+    Sun,
+    Moon,
+}
+
+impl Luminary {
+    pub const ALL: [Luminary; 2] = [Luminary::Sun, Luminary::Moon];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Luminary::Sun => "Sun",
+            Luminary::Moon => "Moon",
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Luminary::Sun => "☉",
+            Luminary::Moon => "☽",
+        }
+    }
+
+    /// Mean daily motion in longitude, in degrees.
+    fn mean_motion(&self) -> f64 {
+        match self {
+            Luminary::Sun => 0.985_647,
+            Luminary::Moon => 13.176_358,
+        }
+    }
+}
+
+impl FromStr for Luminary {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sun" | "sol" | "☉" => Ok(Luminary::Sun),
+            "moon" | "luna" | "☽" => Ok(Luminary::Moon),
+            _ => Err(format!("Unknown body: {} (expected sun or moon)", s).into()),
+        }
+    }
+}
+
+/// The moment a body enters a sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ingress {
+    pub body: Luminary,
+    /// Index into `ThelemicDate::SIGNS` of the sign entered
+    pub sign: usize,
+    pub time: DateTime<Tz>,
+    /// Language the sign is named in when displayed
+    pub language: Language,
+    /// Style the ingress is displayed in
+    pub style: Style,
+}
+
+impl Ingress {
+    pub fn sign_name(&self) -> &'static str {
+        ThelemicDate::SIGNS[self.sign].0
+    }
+
+    /// The ingress without its time, e.g. "☽ enters Virgo", "☽ → ♍" or
+    /// "Moon enters Virgo".
+    pub fn event(&self) -> String {
+        match self.style {
+            Style::Text => format!("{} {}", self.body.glyph(), self.language.ingress(self.sign)),
+            Style::Glyph => format!("{} → {}", self.body.glyph(), ThelemicDate::SIGNS[self.sign].1),
+            Style::Ascii => format!("{} {}", self.body.name(), to_ascii(&self.language.ingress(self.sign))),
+        }
+    }
+}
+
+impl fmt::Display for Ingress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.event(), self.time.format("%H:%M"))
+    }
+}
+
+impl ThelemicDate {
+    /// Finds the next time after `from` that `body` enters a new sign.
+    pub fn next_ingress(&self, body: Luminary, from: &DateTime<Tz>) -> Result<Ingress, Box<dyn std::error::Error>> {
+        // Longitude in the selected zodiac
+        let longitude = |jd: f64| {
+            let long = match body {
+                Luminary::Sun => self.sun_longitude(jd),
+                Luminary::Moon => self.moon_longitude(jd),
+            };
+            (long.to_degrees() - self.zodiac_offset(jd)).rem_euclid(360.0)
+        };
+        let start = Self::julian_day(&from.naive_utc());
+        let sign = ((longitude(start) / 30.0).floor() as usize + 1) % 12;
+        let boundary = sign as f64 * 30.0;
+
+        // Both bodies move steadily forward, so stepping by the remaining
+        // distance at the mean rate converges on the boundary
+        let mut jd = start;
+        for _ in 0..50 {
+            let remaining = (boundary - longitude(jd) + 180.0).rem_euclid(360.0) - 180.0;
+            jd += remaining / body.mean_motion();
+            if remaining.abs() * 86400.0 / body.mean_motion() < 0.5 {
+                break;
+            }
+        }
+
+        let time: DateTime<Utc> = Self::utc_from_julian_day(jd).ok_or("Time out of range")?;
+        Ok(Ingress {
+            body,
+            sign,
+            time: time.with_timezone(&from.timezone()),
+            language: self.language,
+            style: self.style,
+        })
+    }
+
+    /// Finds every ingress of `bodies` between two instants, in time order.
+    pub fn ingresses(&self, bodies: &[Luminary], from: &DateTime<Tz>, to: &DateTime<Tz>)
+        -> Result<Vec<Ingress>, Box<dyn std::error::Error>> {
+        let mut found = Vec::new();
+        for &body in bodies {
+            let mut at = *from;
+            loop {
+                let ingress = self.next_ingress(body, &at)?;
+                if ingress.time >= *to {
+                    break;
+                }
+                // Continue from just past the ingress so it is not found again
+                at = ingress.time + chrono::Duration::minutes(1);
+                found.push(ingress);
+            }
+        }
+        found.sort_by_key(|ingress| ingress.time);
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::UTC;
+
+    #[test]
+    fn finds_the_suns_ingresses() {
+        let date_data = ThelemicDate::new().with_cache(None);
+        let from = UTC.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        let to = UTC.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap();
+        let ingresses = date_data.ingresses(&[Luminary::Sun], &from, &to).unwrap();
+        let signs: Vec<_> = ingresses.iter().map(|ingress| ingress.sign_name()).collect();
+        assert_eq!(signs, ["Aries", "Taurus", "Gemini", "Cancer"]);
+        assert!(ingresses.iter().all(|ingress| ingress.body == Luminary::Sun));
+        assert_eq!(ingresses[0].event(), "☉ enters Aries");
+
+        // The equinox and solstice, published as 2025 March 20 09:01 UT and
+        // June 21 02:42 UT
+        let equinox = UTC.with_ymd_and_hms(2025, 3, 20, 9, 1, 0).unwrap();
+        let solstice = UTC.with_ymd_and_hms(2025, 6, 21, 2, 42, 0).unwrap();
+        assert!((ingresses[0].time - equinox).num_seconds().abs() < 60, "{}", ingresses[0].time);
+        assert!((ingresses[3].time - solstice).num_seconds().abs() < 60, "{}", ingresses[3].time);
+    }
+}
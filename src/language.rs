@@ -0,0 +1,355 @@
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{Anno, HouseSystem, LunarPhase};
+
+/// Language the date line is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    /// The traditional form: English sign names, Latin weekdays and era
+    #[default]
+    English,
+    Latin,
+    Spanish,
+    Portuguese,
+    German,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::English, Language::Latin, Language::Spanish,
+        Language::Portuguese, Language::German, Language::French
+    ];
+
+    /// ISO 639-1 code of the language.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Latin => "la",
+            Language::Spanish => "es",
+            Language::Portuguese => "pt",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    /// Reads a locale such as `LANG`'s "de_DE.UTF-8", falling back to English
+    /// for "C", "POSIX" and any language without a table.
+    pub fn from_locale(locale: &str) -> Self {
+        let code = locale.split(['_', '.', '@', '-']).next().unwrap_or_default();
+        code.parse().unwrap_or_default()
+    }
+
+    /// Name of a sign, by index into `ThelemicDate::SIGNS`, as it stands
+    /// alone, e.g. in a list of house cusps.
+    pub fn sign(&self, sign: usize) -> &'static str {
+        let names = match self {
+            Language::English => [
+                "Aries", "Taurus", "Gemini", "Cancer", "Leo", "Virgo",
+                "Libra", "Scorpio", "Sagittarius", "Capricorn", "Aquarius", "Pisces",
+            ],
+            Language::Latin => [
+                "Aries", "Taurus", "Gemini", "Cancer", "Leo", "Virgo",
+                "Libra", "Scorpius", "Sagittarius", "Capricornus", "Aquarius", "Pisces",
+            ],
+            Language::Spanish => [
+                "Aries", "Tauro", "Géminis", "Cáncer", "Leo", "Virgo",
+                "Libra", "Escorpio", "Sagitario", "Capricornio", "Acuario", "Piscis",
+            ],
+            Language::Portuguese => [
+                "Áries", "Touro", "Gêmeos", "Câncer", "Leão", "Virgem",
+                "Libra", "Escorpião", "Sagitário", "Capricórnio", "Aquário", "Peixes",
+            ],
+            Language::German => [
+                "Widder", "Stier", "Zwillinge", "Krebs", "Löwe", "Jungfrau",
+                "Waage", "Skorpion", "Schütze", "Steinbock", "Wassermann", "Fische",
+            ],
+            Language::French => [
+                "Bélier", "Taureau", "Gémeaux", "Cancer", "Lion", "Vierge",
+                "Balance", "Scorpion", "Sagittaire", "Capricorne", "Verseau", "Poissons",
+            ],
+        };
+        names[sign % 12]
+    }
+
+    /// Where a body stands, e.g. "in 1º Leo", "in 1º Leone" or "à 1º du Lion",
+    /// from the degree as written and the sign's index.
+    pub fn position(&self, degree: &str, sign: usize) -> String {
+        match self {
+            Language::English => format!("in {} {}", degree, self.sign(sign)),
+            // "in" takes the ablative
+            Language::Latin => {
+                const ABLATIVE: [&str; 12] = [
+                    "Ariete", "Tauro", "Geminis", "Cancro", "Leone", "Virgine",
+                    "Libra", "Scorpione", "Sagittario", "Capricorno", "Aquario", "Piscibus",
+                ];
+                format!("in {} {}", degree, ABLATIVE[sign % 12])
+            }
+            Language::Spanish => format!("en {} de {}", degree, self.sign(sign)),
+            Language::Portuguese => format!("em {} de {}", degree, self.sign(sign)),
+            Language::German => format!("auf {} {}", degree, self.sign(sign)),
+            // "de" with the article contracts to "du" and "des"
+            Language::French => {
+                const ARTICLES: [&str; 12] = [
+                    "du", "du", "des", "du", "du", "de la",
+                    "de la", "du", "du", "du", "du", "des",
+                ];
+                format!("à {} {} {}", degree, ARTICLES[sign % 12], self.sign(sign))
+            }
+        }
+    }
+
+    /// A body entering a sign, by the sign's index, e.g. "enters Leo",
+    /// "intrat Leonem" or "tritt in den Löwen ein".
+    pub fn ingress(&self, sign: usize) -> String {
+        match self {
+            Language::English => format!("enters {}", self.sign(sign)),
+            // "intrare" takes the accusative
+            Language::Latin => {
+                const ACCUSATIVE: [&str; 12] = [
+                    "Arietem", "Taurum", "Geminos", "Cancrum", "Leonem", "Virginem",
+                    "Libram", "Scorpionem", "Sagittarium", "Capricornum", "Aquarium", "Pisces",
+                ];
+                format!("intrat {}", ACCUSATIVE[sign % 12])
+            }
+            Language::Spanish => format!("entra en {}", self.sign(sign)),
+            Language::Portuguese => format!("entra em {}", self.sign(sign)),
+            // The accusative, where Löwe and Schütze are weak nouns
+            Language::German => {
+                const ACCUSATIVE: [&str; 12] = [
+                    "den Widder", "den Stier", "die Zwillinge", "den Krebs", "den Löwen", "die Jungfrau",
+                    "die Waage", "den Skorpion", "den Schützen", "den Steinbock", "den Wassermann", "die Fische",
+                ];
+                format!("tritt in {} ein", ACCUSATIVE[sign % 12])
+            }
+            Language::French => {
+                const ARTICLES: [&str; 12] = [
+                    "le", "le", "les", "le", "le", "la",
+                    "la", "le", "le", "le", "le", "les",
+                ];
+                format!("entre dans {} {}", ARTICLES[sign % 12], self.sign(sign))
+            }
+        }
+    }
+
+    /// The planetary day, by index into `ThelemicDate::DAYS_OF_WEEK`, e.g.
+    /// "dies Mercurii" or "día del Sol".
+    pub fn weekday(&self, day: usize) -> &'static str {
+        let names = match self {
+            Language::English | Language::Latin => [
+                "dies Lunae", "dies Martis", "dies Mercurii", "dies Jovis",
+                "dies Veneris", "dies Saturnii", "dies Solis",
+            ],
+            Language::Spanish => [
+                "día de la Luna", "día de Marte", "día de Mercurio", "día de Júpiter",
+                "día de Venus", "día de Saturno", "día del Sol",
+            ],
+            Language::Portuguese => [
+                "dia da Lua", "dia de Marte", "dia de Mercúrio", "dia de Júpiter",
+                "dia de Vênus", "dia de Saturno", "dia do Sol",
+            ],
+            Language::German => [
+                "Tag des Mondes", "Tag des Mars", "Tag des Merkur", "Tag des Jupiter",
+                "Tag der Venus", "Tag des Saturn", "Tag der Sonne",
+            ],
+            Language::French => [
+                "jour de la Lune", "jour de Mars", "jour de Mercure", "jour de Jupiter",
+                "jour de Vénus", "jour de Saturne", "jour du Soleil",
+            ],
+        };
+        names[day % 7]
+    }
+
+    /// The ruler of a planetary day on its own, by index into
+    /// `ThelemicDate::DAYS_OF_WEEK`: the Latin genitive ("Mercurii") in
+    /// English and Latin, the planet's name ("Merkur") in the others.
+    pub fn day_name(&self, day: usize) -> &'static str {
+        let names = match self {
+            Language::English | Language::Latin => [
+                "Lunae", "Martis", "Mercurii", "Jovis", "Veneris", "Saturnii", "Solis",
+            ],
+            Language::Spanish => ["Luna", "Marte", "Mercurio", "Júpiter", "Venus", "Saturno", "Sol"],
+            Language::Portuguese => ["Lua", "Marte", "Mercúrio", "Júpiter", "Vênus", "Saturno", "Sol"],
+            Language::German => ["Mond", "Mars", "Merkur", "Jupiter", "Venus", "Saturn", "Sonne"],
+            Language::French => ["Lune", "Mars", "Mercure", "Jupiter", "Vénus", "Saturne", "Soleil"],
+        };
+        names[day % 7]
+    }
+
+    /// A planetary hour, by index of its ruler into
+    /// `ThelemicDate::DAYS_OF_WEEK`, e.g. "hora Saturnii" or "heure du Soleil".
+    pub fn hour(&self, ruler: usize) -> &'static str {
+        let names = match self {
+            Language::English | Language::Latin => [
+                "hora Lunae", "hora Martis", "hora Mercurii", "hora Jovis",
+                "hora Veneris", "hora Saturnii", "hora Solis",
+            ],
+            Language::Spanish => [
+                "hora de la Luna", "hora de Marte", "hora de Mercurio", "hora de Júpiter",
+                "hora de Venus", "hora de Saturno", "hora del Sol",
+            ],
+            Language::Portuguese => [
+                "hora da Lua", "hora de Marte", "hora de Mercúrio", "hora de Júpiter",
+                "hora de Vênus", "hora de Saturno", "hora do Sol",
+            ],
+            Language::German => [
+                "Stunde des Mondes", "Stunde des Mars", "Stunde des Merkur", "Stunde des Jupiter",
+                "Stunde der Venus", "Stunde des Saturn", "Stunde der Sonne",
+            ],
+            Language::French => [
+                "heure de la Lune", "heure de Mars", "heure de Mercure", "heure de Jupiter",
+                "heure de Vénus", "heure de Saturne", "heure du Soleil",
+            ],
+        };
+        names[ruler % 7]
+    }
+
+    /// Name of a phase of the Moon, e.g. "First Quarter", "Luna dimidia
+    /// crescens" or "Erstes Viertel".
+    pub fn phase(&self, phase: LunarPhase) -> &'static str {
+        let names = match self {
+            Language::English => return phase.name(),
+            Language::Latin => return phase.latin_name(),
+            Language::Spanish => [
+                "Luna nueva", "Luna creciente", "Cuarto creciente", "Gibosa creciente",
+                "Luna llena", "Gibosa menguante", "Cuarto menguante", "Luna menguante",
+            ],
+            Language::Portuguese => [
+                "Lua nova", "Lua crescente", "Quarto crescente", "Crescente gibosa",
+                "Lua cheia", "Minguante gibosa", "Quarto minguante", "Lua minguante",
+            ],
+            Language::German => [
+                "Neumond", "Zunehmende Sichel", "Erstes Viertel", "Zunehmender Mond",
+                "Vollmond", "Abnehmender Mond", "Letztes Viertel", "Abnehmende Sichel",
+            ],
+            Language::French => [
+                "Nouvelle lune", "Premier croissant", "Premier quartier", "Gibbeuse croissante",
+                "Pleine lune", "Gibbeuse décroissante", "Dernier quartier", "Dernier croissant",
+            ],
+        };
+        names[phase as usize]
+    }
+
+    /// Labels of the Ascendant, Midheaven and local sidereal time on the
+    /// line of house cusps, e.g. "Asc", "MC" and "LST".
+    pub fn angle_labels(&self) -> [&'static str; 3] {
+        match self {
+            Language::English => ["Asc", "MC", "LST"],
+            Language::Latin | Language::Spanish | Language::Portuguese | Language::French => ["Asc", "MC", "TSL"],
+            Language::German => ["AC", "MC", "OSZ"],
+        }
+    }
+
+    /// Name of a house system, e.g. "Whole Sign" or "Ganzzeichen".
+    pub fn house_system(&self, system: HouseSystem) -> &'static str {
+        match (self, system) {
+            (_, HouseSystem::Placidus | HouseSystem::Koch) | (Language::English, _) => system.name(),
+            (Language::Latin, HouseSystem::WholeSign) => "Signa integra",
+            (Language::Latin, HouseSystem::Equal) => "Domus aequales",
+            (Language::Spanish, HouseSystem::WholeSign) => "Signo entero",
+            (Language::Spanish, HouseSystem::Equal) => "Casas iguales",
+            (Language::Portuguese, HouseSystem::WholeSign) => "Signo inteiro",
+            (Language::Portuguese, HouseSystem::Equal) => "Casas iguais",
+            (Language::German, HouseSystem::WholeSign) => "Ganzzeichen",
+            (Language::German, HouseSystem::Equal) => "Äqual",
+            (Language::French, HouseSystem::WholeSign) => "Signes entiers",
+            (Language::French, HouseSystem::Equal) => "Maisons égales",
+        }
+    }
+
+    /// The era after a year, e.g. "æræ legis" or "de l'ère de la Loi", or
+    /// the era it comes before.
+    pub fn era(&self, before_era: bool) -> &'static str {
+        match (self, before_era) {
+            (Language::English | Language::Latin, false) => "æræ legis",
+            (Language::English | Language::Latin, true) => "ante æram legis",
+            (Language::Spanish, false) => "de la era de la Ley",
+            (Language::Spanish, true) => "antes de la era de la Ley",
+            (Language::Portuguese, false) => "da era da Lei",
+            (Language::Portuguese, true) => "antes da era da Lei",
+            (Language::German, false) => "der Ära des Gesetzes",
+            (Language::German, true) => "vor der Ära des Gesetzes",
+            (Language::French, false) => "de l'ère de la Loi",
+            (Language::French, true) => "avant l'ère de la Loi",
+        }
+    }
+
+    /// A Gregorian date written out, e.g. "23 July 2025", "23 Iulii 2025"
+    /// or "23. Juli 2025".
+    pub fn date(&self, date: NaiveDate) -> String {
+        let months = match self {
+            Language::English => [
+                "January", "February", "March", "April", "May", "June",
+                "July", "August", "September", "October", "November", "December",
+            ],
+            // The genitive, as in "die 23 mensis Iulii"
+            Language::Latin => [
+                "Ianuarii", "Februarii", "Martii", "Aprilis", "Maii", "Iunii",
+                "Iulii", "Augusti", "Septembris", "Octobris", "Novembris", "Decembris",
+            ],
+            Language::Spanish => [
+                "enero", "febrero", "marzo", "abril", "mayo", "junio",
+                "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre",
+            ],
+            Language::Portuguese => [
+                "janeiro", "fevereiro", "março", "abril", "maio", "junho",
+                "julho", "agosto", "setembro", "outubro", "novembro", "dezembro",
+            ],
+            Language::German => [
+                "Januar", "Februar", "März", "April", "Mai", "Juni",
+                "Juli", "August", "September", "Oktober", "November", "Dezember",
+            ],
+            Language::French => [
+                "janvier", "février", "mars", "avril", "mai", "juin",
+                "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+            ],
+        };
+        let month = months[date.month0() as usize];
+        match self {
+            Language::English | Language::Latin | Language::French => format!("{} {} {}", date.day(), month, date.year()),
+            Language::Spanish | Language::Portuguese => format!("{} de {} de {}", date.day(), month, date.year()),
+            Language::German => format!("{}. {} {}", date.day(), month, date.year()),
+        }
+    }
+
+    /// The year with its era, e.g. "Anno Vxi æræ legis" or "An Vxi de l'ère
+    /// de la Loi".
+    pub fn anno(&self, anno: &Anno) -> String {
+        format!("{} {} {}", self.year(false), anno, self.era(anno.is_before_era()))
+    }
+
+    /// The word put before an Anno, e.g. "Anno" or "Jahr", or its short form
+    /// for headings, e.g. "An".
+    pub fn year(&self, short: bool) -> &'static str {
+        match (self, short) {
+            (Language::English | Language::Latin, false) => "Anno",
+            (Language::English | Language::Latin, true) => "An",
+            (Language::Spanish, _) => "Año",
+            (Language::Portuguese, _) => "Ano",
+            (Language::German, _) => "Jahr",
+            (Language::French, _) => "An",
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "la" | "latin" | "latina" => Ok(Language::Latin),
+            "es" | "spanish" | "español" | "espanol" => Ok(Language::Spanish),
+            "pt" | "portuguese" | "português" | "portugues" => Ok(Language::Portuguese),
+            "de" | "german" | "deutsch" => Ok(Language::German),
+            "fr" | "french" | "français" | "francais" => Ok(Language::French),
+            _ => Err(format!(
+                "Unknown language: {} (expected one of {})",
+                s,
+                Language::ALL.iter().map(|language| language.code()).collect::<Vec<_>>().join(", ")
+            ).into()),
+        }
+    }
+}
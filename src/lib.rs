@@ -0,0 +1,844 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, Timelike, Weekday, TimeZone, Utc};
+use chrono_tz::Tz;
+use tzf_rs::DefaultFinder;
+use astro::time;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::fmt;
+
+mod anno;
+mod apparent;
+mod cache;
+mod degrees;
+mod ephemeris;
+mod feasts;
+mod format;
+mod gazetteer;
+mod geocoder;
+mod hours;
+mod houses;
+mod ics;
+mod ingress;
+mod language;
+mod location;
+mod phase;
+mod planets;
+mod preset;
+mod resh;
+mod reverse;
+mod style;
+mod template;
+mod zodiac;
+
+pub use anno::Anno;
+pub use cache::{CachedLocation, LocationCache};
+pub use degrees::{DegreeFormat, Precision};
+pub use ephemeris::{parse_step, EphemerisFormat, EphemerisRow};
+pub use feasts::{Feast, Observance};
+pub use format::OutputFormat;
+pub use gazetteer::{City, Gazetteer};
+pub use geocoder::{parse_geocoder, Geocoder, Nominatim, Photon, StaticFile};
+pub use hours::PlanetaryHour;
+pub use houses::{HouseSystem, Houses};
+pub use ics::{write_ics, CalendarEvent, EventTime};
+pub use ingress::{Ingress, Luminary};
+pub use language::Language;
+pub use location::{Location, ResolvedLocation};
+pub use phase::{LunarPhase, MoonPhase, Syzygy};
+pub use planets::{Planet, PlanetPosition};
+pub use preset::Preset;
+pub use resh::{Adoration, ReshDay};
+pub use reverse::{DateWindow, SignDegree, ThelemicDateQuery};
+pub use style::{to_ascii, Style};
+pub use template::Template;
+pub use zodiac::{Ayanamsa, Zodiac};
+
+/// A geocoded place: latitude, longitude and IANA timezone name.
+type Geopos = (f64, f64, String);
+
+/// Calculator for Thelemic dates, holding the gazetteer, geocoder and
+/// timezone finder used to resolve locations.
+pub struct ThelemicDate {
+    finder: DefaultFinder,
+    gazetteer: Gazetteer,
+    geocoder: Box<dyn Geocoder>,
+    /// Whether the geocoder was chosen rather than left as the default
+    custom_geocoder: bool,
+    cache: Option<LocationCache>,
+    offline: bool,
+    refresh_location: bool,
+    planets: bool,
+    houses: Option<HouseSystem>,
+    moon_phase: Option<Language>,
+    planetary_hour: bool,
+    sunrise_day: bool,
+    zodiac: Zodiac,
+    degree_format: DegreeFormat,
+    geometric: bool,
+    language: Language,
+    style: Style,
+    /// Warnings raised while resolving locations, see `take_warnings`
+    warnings: RefCell<Vec<String>>,
+}
+
+impl ThelemicDate {
+    pub fn new() -> Self {
+        ThelemicDate {
+            finder: DefaultFinder::new(),
+            gazetteer: Gazetteer::embedded(),
+            geocoder: Box::new(Nominatim::new(Nominatim::DEFAULT_ENDPOINT)),
+            custom_geocoder: false,
+            cache: LocationCache::open_default(),
+            offline: false,
+            refresh_location: false,
+            planets: false,
+            houses: None,
+            moon_phase: None,
+            planetary_hour: false,
+            sunrise_day: false,
+            zodiac: Zodiac::Tropical,
+            degree_format: DegreeFormat::default(),
+            geometric: false,
+            language: Language::English,
+            style: Style::Text,
+            warnings: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Resolves locations from the embedded gazetteer (and the location
+    /// cache) only, never falling back to OpenStreetMap.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Replaces the geocoder, OpenStreetMap's Nominatim by default. Unlike
+    /// the default, a geocoder chosen here is asked before the gazetteer.
+    pub fn with_geocoder(mut self, geocoder: Box<dyn Geocoder>) -> Self {
+        self.geocoder = geocoder;
+        self.custom_geocoder = true;
+        self
+    }
+
+    /// Replaces the location cache, or disables it with `None`.
+    pub fn with_cache(mut self, cache: Option<LocationCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Ignores cached geocoding results, storing fresh ones in their place.
+    pub fn with_refresh_location(mut self, refresh: bool) -> Self {
+        self.refresh_location = refresh;
+        self
+    }
+
+    /// Also computes the positions of Mercury through Pluto.
+    pub fn with_planets(mut self, planets: bool) -> Self {
+        self.planets = planets;
+        self
+    }
+
+    /// Also casts the Ascendant, Midheaven and house cusps under `system`
+    /// when the location's coordinates are known.
+    pub fn with_houses(mut self, system: Option<HouseSystem>) -> Self {
+        self.houses = system;
+        self
+    }
+
+    /// Also computes the Moon's phase, illumination and age, naming the
+    /// phase in the given language.
+    pub fn with_moon_phase(mut self, language: Option<Language>) -> Self {
+        self.moon_phase = language;
+        self
+    }
+
+    /// Also finds the planetary hour, which needs the location's coordinates.
+    pub fn with_planetary_hour(mut self, planetary_hour: bool) -> Self {
+        self.planetary_hour = planetary_hour;
+        self
+    }
+
+    /// Starts each weekday at local sunrise, as the planetary day does,
+    /// rather than at midnight. Needs the location's coordinates.
+    pub fn with_sunrise_day(mut self, sunrise_day: bool) -> Self {
+        self.sunrise_day = sunrise_day;
+        self
+    }
+
+    /// Measures every body in the given zodiac, tropical by default.
+    pub fn with_zodiac(mut self, zodiac: Zodiac) -> Self {
+        self.zodiac = zodiac;
+        self
+    }
+
+    /// Sets how the degree of each body within its sign is counted and
+    /// written, whole truncated degrees from 0 by default.
+    pub fn with_degree_format(mut self, format: DegreeFormat) -> Self {
+        self.degree_format = format;
+        self
+    }
+
+    /// Reports geometric positions, referred to the mean equinox and with
+    /// Universal Time taken for Terrestrial Time, instead of apparent ones
+    /// corrected for ΔT, nutation and aberration.
+    pub fn with_geometric(mut self, geometric: bool) -> Self {
+        self.geometric = geometric;
+        self
+    }
+
+    /// Writes sign names, weekdays and the era in the given language.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Draws the date line with words, with glyphs throughout or in plain
+    /// ASCII.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn cache(&self) -> Option<&LocationCache> {
+        self.cache.as_ref()
+    }
+
+    pub const SIGNS: [(&'static str, &'static str); 12] = [
+        ("Aries", "♈"), ("Taurus", "♉"), ("Gemini", "♊"), ("Cancer", "♋"),
+        ("Leo", "♌"), ("Virgo", "♍"), ("Libra", "♎"), ("Scorpio", "♏"),
+        ("Sagittarius", "♐"), ("Capricorn", "♑"), ("Aquarius", "♒"), ("Pisces", "♓")
+    ];
+
+    pub const DAYS_OF_WEEK: [&'static str; 7] = [
+        "Lunae", "Martis", "Mercurii", "Jovis",
+        "Veneris", "Saturnii", "Solis"
+    ];
+
+    /// Geocodes a place name. A local geocoder (such as a places file) or
+    /// one configured with `with_geocoder` is asked first, then the embedded
+    /// gazetteer; the default remote geocoder is only asked after that.
+    fn get_geopos(&self, location: &str) -> Result<Geopos, Box<dyn std::error::Error>> {
+        // A chosen geocoder, such as a self-hosted or stand-in server, must
+        // not be bypassed for the places the gazetteer happens to know
+        let geocoder_first = self.custom_geocoder || !self.geocoder.is_remote();
+        if geocoder_first {
+            if let Some(found) = self.geocode(location)? {
+                return Ok(found);
+            }
+        }
+        if let Some(city) = self.gazetteer.search(location) {
+            let timezone_name = self.finder.get_tz_name(city.longitude, city.latitude);
+            return Ok((city.latitude, city.longitude, timezone_name.to_string()));
+        }
+        if !geocoder_first {
+            if let Some(found) = self.geocode(location)? {
+                return Ok(found);
+            }
+        }
+        if self.offline {
+            Err(format!("Location not found in the offline gazetteer: {}", location).into())
+        } else {
+            Err("Location not found".into())
+        }
+    }
+
+    /// Asks the geocoder for a place, through the location cache when it is
+    /// remote. Remote geocoders are not asked in offline mode.
+    fn geocode(&self, location: &str) -> Result<Option<Geopos>, Box<dyn std::error::Error>> {
+        let from_coords = |(lat, lon): (f64, f64)| {
+            let timezone_name = self.finder.get_tz_name(lon, lat);
+            (lat, lon, timezone_name.to_string())
+        };
+        if !self.geocoder.is_remote() {
+            return Ok(self.geocoder.forward(location)?.map(from_coords));
+        }
+
+        let source = self.geocoder.source();
+        if !self.refresh_location {
+            if let Some(entry) = self.cache.as_ref().and_then(|cache| cache.get(location, &source)) {
+                return Ok(Some((entry.latitude, entry.longitude, entry.timezone)));
+            }
+        }
+        if self.offline {
+            // An expired entry cannot be refreshed without the network, and
+            // is still better than no location at all
+            let entry = self.cache.as_ref().and_then(|cache| cache.get_stale(location, &source));
+            if let Some(entry) = entry {
+                self.warn(format!("Using an expired cached location for {} (offline)", location));
+                return Ok(Some((entry.latitude, entry.longitude, entry.timezone)));
+            }
+            return Ok(None);
+        }
+
+        let Some((lat, lon, timezone_name)) = self.geocoder.forward(location)?.map(from_coords) else {
+            return Ok(None);
+        };
+        if let Some(cache) = &self.cache {
+            // An unwritable cache only costs a lookup next time, so it
+            // must not stop the date from being shown
+            let _ = cache.insert(location, lat, lon, &timezone_name, &source);
+        }
+        Ok(Some((lat, lon, timezone_name)))
+    }
+
+    /// Records a warning for the caller, once however often it is raised.
+    fn warn(&self, warning: String) {
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// Takes the warnings raised since they were last taken, such as an
+    /// expired cached location being used offline. They do not stop a date
+    /// from being computed, so it is up to the caller to show them.
+    pub fn take_warnings(&self) -> Vec<String> {
+        self.warnings.take()
+    }
+
+    /// Resolves a location to its timezone and, where known, its coordinates.
+    /// Place names are geocoded once per call.
+    pub fn resolve(&self, location: &Location) -> Result<ResolvedLocation, Box<dyn std::error::Error>> {
+        let (latitude, longitude, tz_name) = match location {
+            Location::Place(place) => {
+                let (lat, lon, tz_name) = self.get_geopos(place)?;
+                (Some(lat), Some(lon), tz_name)
+            }
+            Location::Coordinates { latitude, longitude, timezone } => {
+                let tz_name = match timezone {
+                    Some(tz) => tz.name().to_string(),
+                    None => self.finder.get_tz_name(*longitude, *latitude).to_string(),
+                };
+                (Some(*latitude), Some(*longitude), tz_name)
+            }
+            Location::Timezone(tz) => (None, None, tz.name().to_string()),
+        };
+        let timezone = tz_name.parse::<Tz>()
+            .map_err(|e| format!("Invalid timezone: {}", e))?;
+        Ok(ResolvedLocation { latitude, longitude, timezone })
+    }
+
+    pub fn get_sign_from_longitude(&self, longitude: f64) -> &'static str {
+        let degree = longitude * 180.0 / PI;
+        let normalized_degree = if degree < 0.0 { degree + 360.0 } else { degree };
+        let sign_index = (normalized_degree / 30.0) as usize;
+        Self::SIGNS[sign_index % 12].0
+    }
+
+    pub fn get_degree_in_sign(&self, longitude: f64) -> i32 {
+        let degree = longitude * 180.0 / PI;
+        let normalized_degree = if degree < 0.0 { degree + 360.0 } else { degree };
+        (normalized_degree % 30.0) as i32
+    }
+
+    /// Mean length of the tropical year in days, used to step towards an equinox.
+    const TROPICAL_YEAR: f64 = 365.2422;
+
+    /// Julian Day of the Unix epoch (1970-01-01T00:00:00Z).
+    const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+    pub fn julian_day(naive_utc: &NaiveDateTime) -> f64 {
+        time::julian_day(
+            &time::Date {
+                year: naive_utc.year() as i16,
+                month: naive_utc.month() as u8,
+                decimal_day: naive_utc.day() as f64
+                    + naive_utc.hour() as f64 / 24.0
+                    + naive_utc.minute() as f64 / 1440.0
+                    + naive_utc.second() as f64 / 86400.0,
+                cal_type: time::CalType::Gregorian,
+            }
+        )
+    }
+
+    pub fn utc_from_julian_day(jd: f64) -> Option<DateTime<Utc>> {
+        let millis = ((jd - Self::UNIX_EPOCH_JD) * 86_400_000.0).round() as i64;
+        DateTime::from_timestamp_millis(millis)
+    }
+
+    /// Finds the Julian Day (UT) of the vernal equinox of `year`, i.e. the moment
+    /// the Sun's apparent geocentric ecliptic longitude crosses 0°.
+    pub fn vernal_equinox_jd(year: i32) -> f64 {
+        Self::solar_longitude_jd(year, 0.0)
+    }
+
+    /// Finds the Julian Day (UT) in `year` at which the Sun's apparent
+    /// geocentric ecliptic longitude reaches `longitude` degrees, counting the
+    /// year from the vernal equinox: 90 gives the June solstice, 180 the
+    /// September equinox and 270 the December solstice.
+    pub fn solar_longitude_jd(year: i32, longitude: f64) -> f64 {
+        let mut jde = time::julian_day(&time::Date {
+            year: year as i16,
+            month: 3,
+            decimal_day: 20.5,
+            cal_type: time::CalType::Gregorian,
+        }) + longitude.rem_euclid(360.0) / 360.0 * Self::TROPICAL_YEAR;
+
+        for _ in 0..50 {
+            // Published equinox and solstice times refer to the apparent position
+            let apparent_long = Self::apparent_sun_longitude(jde);
+
+            // Signed distance (in degrees) still to travel, in (-180, 180]
+            let mut delta = (longitude - apparent_long.to_degrees()) % 360.0;
+            if delta <= -180.0 {
+                delta += 360.0;
+            } else if delta > 180.0 {
+                delta -= 360.0;
+            }
+            jde += delta / 360.0 * Self::TROPICAL_YEAR;
+            if delta.abs() < 1e-7 {
+                break;
+            }
+        }
+
+        // The solar theory runs on Terrestrial Time; convert back to UT
+        jde - Self::delta_t(jde)
+    }
+
+    /// Returns the vernal equinox of `year` as an instant in the observer's timezone.
+    pub fn vernal_equinox(year: i32, tz: &Tz) -> Result<DateTime<Tz>, Box<dyn std::error::Error>> {
+        Self::check_year(year)?;
+        let equinox = Self::utc_from_julian_day(Self::vernal_equinox_jd(year))
+            .ok_or("Equinox out of range")?;
+        Ok(equinox.with_timezone(tz))
+    }
+
+    /// Rejects years the astronomical routines cannot represent.
+    fn check_year(year: i32) -> Result<(), Box<dyn std::error::Error>> {
+        if i16::try_from(year).is_err() {
+            return Err(format!("Year {} is outside the supported range", year).into());
+        }
+        Ok(())
+    }
+
+    /// Finds the Anno of an instant, counted from the Equinox of the Gods
+    /// (1904) and rolling over at the exact instant of each vernal equinox.
+    pub fn anno(dt: &DateTime<Tz>) -> Result<Anno, Box<dyn std::error::Error>> {
+        let equinox = Self::vernal_equinox(dt.year(), &dt.timezone())?;
+        if *dt < equinox {
+            Anno::new(dt.year() - 1905)
+        } else {
+            Anno::new(dt.year() - 1904)
+        }
+    }
+
+    /// Builds a `Position` from an ecliptic longitude in radians, with its
+    /// sign and degree counted under the selected degree format.
+    pub fn position(&self, longitude: f64) -> Position {
+        let longitude = longitude.to_degrees().rem_euclid(360.0);
+        let (sign, degree, _) = self.degree_format.split(longitude);
+        Position {
+            longitude,
+            sign: Self::SIGNS[sign].0,
+            degree,
+            format: self.degree_format,
+            language: self.language,
+            style: self.style,
+        }
+    }
+
+    pub fn weekday_to_index(weekday: Weekday) -> usize {
+        match weekday {
+            Weekday::Mon => 0,
+            Weekday::Tue => 1,
+            Weekday::Wed => 2,
+            Weekday::Thu => 3,
+            Weekday::Fri => 4,
+            Weekday::Sat => 5,
+            Weekday::Sun => 6,
+        }
+    }
+
+    /// Computes the Thelemic date for an instant in the observer's timezone.
+    pub fn at(&self, dt: &DateTime<Tz>) -> Result<ThelemicDateValue, Box<dyn std::error::Error>> {
+        // Thelemic New Year starts at the instant of the vernal equinox
+        let anno = Self::anno(dt)?;
+
+        let ve_weekday = Self::weekday_to_index(dt.weekday());
+
+        // Calculate Julian Day
+        let jd = Self::julian_day(&dt.naive_utc());
+
+        // Get sun position
+        let sun_long = self.sun_longitude(jd);
+
+        // Get moon position
+        let moon_long = self.moon_longitude(jd);
+
+        // Get planet positions, when asked for
+        let planets = if self.planets {
+            Planet::ALL.iter()
+                .filter_map(|planet| {
+                    let long = self.planet_longitude(*planet, jd)?;
+                    Some(PlanetPosition { planet: *planet, position: self.position_at(long, jd) })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(ThelemicDateValue {
+            sun: self.position_at(sun_long, jd),
+            moon: self.position_at(moon_long, jd),
+            planets,
+            moon_phase: self.moon_phase.map(|language| self.moon_phase(jd, language)),
+            weekday: ve_weekday,
+            anno,
+            julian_day: jd,
+            zodiac: self.zodiac,
+            language: self.language,
+            style: self.style,
+            datetime: *dt,
+            latitude: None,
+            longitude: None,
+            planetary_hour: None,
+            houses: None,
+        })
+    }
+
+    /// Computes the Thelemic date for an instant as seen from a resolved
+    /// location, casting house cusps from its coordinates when asked for.
+    pub fn at_location(&self, dt: &DateTime<Tz>, location: &ResolvedLocation)
+        -> Result<ThelemicDateValue, Box<dyn std::error::Error>> {
+        let mut value = self.at(dt)?;
+        value.latitude = location.latitude;
+        value.longitude = location.longitude;
+        if let Some(system) = self.houses {
+            let (latitude, longitude) = location.coordinates()
+                .ok_or("House cusps need coordinates; give a place or --coords rather than a timezone")?;
+            value.houses = Some(self.houses(value.julian_day, latitude, longitude, system)?);
+        }
+        if self.planetary_hour || self.sunrise_day {
+            let (latitude, longitude) = location.coordinates()
+                .ok_or("Planetary hours need coordinates; give a place or --coords rather than a timezone")?;
+            let hour = PlanetaryHour {
+                language: self.language,
+                style: self.style,
+                ..Self::planetary_hour_at(dt, latitude, longitude)?
+            };
+            if self.sunrise_day {
+                value.weekday = hour.day;
+            }
+            if self.planetary_hour {
+                value.planetary_hour = Some(hour);
+            }
+        }
+        Ok(value)
+    }
+
+    pub fn now(&self, location: &Location) -> Result<ThelemicDateValue, Box<dyn std::error::Error>> {
+        let resolved = self.resolve(location)?;
+        let now = Local::now().with_timezone(&resolved.timezone);
+        self.at_location(&now, &resolved)
+    }
+
+    pub fn in_day(&self, year: i32, month: u32, day: u32, hour: u32, minute: u32, location: &Location) 
+        -> Result<ThelemicDateValue, Box<dyn std::error::Error>> {
+        let resolved = self.resolve(location)?;
+        let tz = resolved.timezone;
+        
+        let date = NaiveDate::from_ymd_opt(year, month, day)
+            .ok_or("Invalid date")?;
+        
+        // Create datetime
+        let naive_dt = NaiveDateTime::new(
+            date,
+            chrono::NaiveTime::from_hms_opt(hour, minute, 0).ok_or("Invalid time")?
+        );
+        let dt = tz.from_local_datetime(&naive_dt)
+            .single()
+            .ok_or("Ambiguous local time")?;
+        
+        self.at_location(&dt, &resolved)
+    }
+}
+
+impl Default for ThelemicDate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A body's place on the zodiac.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct Position {
+    /// Geocentric ecliptic longitude, in degrees within [0, 360)
+    pub longitude: f64,
+    pub sign: &'static str,
+    /// Whole degree within the sign, as written under `format`
+    pub degree: i32,
+    /// How the degree is written when the position is displayed
+    #[serde(skip)]
+    pub format: DegreeFormat,
+    /// Language the sign is named in when the position is displayed
+    #[serde(skip)]
+    pub language: Language,
+    /// Style the position is displayed in
+    #[serde(skip)]
+    pub style: Style,
+}
+
+impl Position {
+    /// Index of the sign into `ThelemicDate::SIGNS`.
+    pub fn sign_index(&self) -> usize {
+        ThelemicDate::SIGNS.iter().position(|(name, _)| *name == self.sign).unwrap_or_default()
+    }
+
+    /// Where the body stands, in its language, e.g. "in 1º Leo".
+    pub fn phrase(&self) -> String {
+        self.language.position(&self.angle(), self.sign_index())
+    }
+
+    /// The degree within the sign as written, e.g. "1º" or "1º23'".
+    pub fn degree_text(&self) -> String {
+        self.format.split(self.longitude).2
+    }
+
+    /// The degree as drawn in the position's style, e.g. "1º23'", "1°23'"
+    /// or "1d23'".
+    pub fn angle(&self) -> String {
+        let degree = self.degree_text();
+        match self.style {
+            Style::Text => degree,
+            Style::Glyph => degree.replace('º', "°"),
+            // A trailing degree sign is dropped; one before minutes becomes a "d"
+            Style::Ascii => match degree.strip_suffix('º') {
+                Some(degree) => degree.to_string(),
+                None => degree.replace('º', "d"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = self.sign_index();
+        match self.style {
+            Style::Text => write!(f, "{} {}", self.angle(), self.language.sign(sign)),
+            Style::Glyph => write!(f, "{} {}", self.angle(), ThelemicDate::SIGNS[sign].1),
+            Style::Ascii => write!(f, "{} {}", self.angle(), to_ascii(self.language.sign(sign))),
+        }
+    }
+}
+
+/// A computed Thelemic date, with every component kept apart from its
+/// textual rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThelemicDateValue {
+    pub sun: Position,
+    pub moon: Position,
+    /// Mercury through Pluto, in that order; empty unless planets were
+    /// asked for with `ThelemicDate::with_planets`
+    pub planets: Vec<PlanetPosition>,
+    /// Only computed when asked for with `ThelemicDate::with_moon_phase`
+    pub moon_phase: Option<MoonPhase>,
+    /// Index into `ThelemicDate::DAYS_OF_WEEK`, Monday being 0
+    pub weekday: usize,
+    pub anno: Anno,
+    /// Julian Day (UT) the date was computed for
+    pub julian_day: f64,
+    /// Zodiac the positions are measured in
+    pub zodiac: Zodiac,
+    /// Language `Display` writes the date in
+    pub language: Language,
+    /// Style `Display` draws the date in
+    pub style: Style,
+    /// The instant the date was computed for, in the observer's timezone
+    pub datetime: DateTime<Tz>,
+    /// Observer's latitude in degrees, when known
+    pub latitude: Option<f64>,
+    /// Observer's longitude in degrees, when known
+    pub longitude: Option<f64>,
+    /// Only found when asked for with `ThelemicDate::with_planetary_hour`
+    /// and the location's coordinates are known
+    pub planetary_hour: Option<PlanetaryHour>,
+    /// Angles and house cusps; only cast when asked for with
+    /// `ThelemicDate::with_houses` and the location's coordinates are known
+    pub houses: Option<Houses>,
+}
+
+impl ThelemicDateValue {
+    /// Latin name of the weekday, e.g. "Mercurii".
+    pub fn weekday_name(&self) -> &'static str {
+        ThelemicDate::DAYS_OF_WEEK[self.weekday]
+    }
+}
+
+impl Serialize for ThelemicDateValue {
+    /// Writes every component, with the weekday's Latin name and the
+    /// timezone spelled out beside the values they derive from. Components
+    /// that were not asked for are left out.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ThelemicDateValue", 18)?;
+        state.serialize_field("datetime", &self.datetime)?;
+        state.serialize_field("timezone", self.datetime.timezone().name())?;
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                state.serialize_field("latitude", &latitude)?;
+                state.serialize_field("longitude", &longitude)?;
+            }
+            _ => {
+                state.skip_field("latitude")?;
+                state.skip_field("longitude")?;
+            }
+        }
+        state.serialize_field("julian_day", &self.julian_day)?;
+        state.serialize_field("zodiac", self.zodiac.name())?;
+        match self.zodiac {
+            Zodiac::Sidereal(ayanamsa) => {
+                state.serialize_field("ayanamsa", ayanamsa.name())?;
+                state.serialize_field("ayanamsa_degrees", &ayanamsa.degrees(self.julian_day))?;
+            }
+            Zodiac::Tropical => {
+                state.skip_field("ayanamsa")?;
+                state.skip_field("ayanamsa_degrees")?;
+            }
+        }
+        state.serialize_field("weekday", &self.weekday)?;
+        state.serialize_field("weekday_name", self.weekday_name())?;
+        state.serialize_field("anno", &self.anno)?;
+        state.serialize_field("sun", &self.sun)?;
+        state.serialize_field("moon", &self.moon)?;
+        if self.planets.is_empty() {
+            state.skip_field("planets")?;
+        } else {
+            state.serialize_field("planets", &self.planets)?;
+        }
+        match &self.moon_phase {
+            Some(moon_phase) => state.serialize_field("moon_phase", moon_phase)?,
+            None => state.skip_field("moon_phase")?,
+        }
+        match &self.planetary_hour {
+            Some(hour) => state.serialize_field("planetary_hour", hour)?,
+            None => state.skip_field("planetary_hour")?,
+        }
+        match &self.houses {
+            Some(houses) => state.serialize_field("houses", houses)?,
+            None => state.skip_field("houses")?,
+        }
+        state.end()
+    }
+}
+
+impl fmt::Display for ThelemicDateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Style::Text => {
+                write!(f, "☉ {} : ☽ {} : ", self.sun.phrase(), self.moon.phrase())?;
+                for planet in &self.planets {
+                    write!(f, "{} {} : ", planet.planet.glyph(), planet.position.phrase())?;
+                }
+                if let Some(moon_phase) = &self.moon_phase {
+                    write!(f, "{} : ", moon_phase)?;
+                }
+                write!(f, "{} : ", self.language.weekday(self.weekday))?;
+                if let Some(hour) = &self.planetary_hour {
+                    write!(f, "{} : ", hour)?;
+                }
+                write!(f, "{}", self.language.anno(&self.anno))?;
+            }
+            Style::Glyph => {
+                write!(f, "☉ {} : ☽ {} : ", self.sun, self.moon)?;
+                for planet in &self.planets {
+                    write!(f, "{} {} : ", planet.planet.glyph(), planet.position)?;
+                }
+                if let Some(moon_phase) = &self.moon_phase {
+                    write!(f, "{} {:.0}% {:.1}d : ", moon_phase.phase.glyph(), moon_phase.illumination, moon_phase.age)?;
+                }
+                write!(f, "{} : ", hours::RULER_GLYPHS[self.weekday])?;
+                if let Some(hour) = &self.planetary_hour {
+                    write!(f, "{} : ", hour)?;
+                }
+                write!(f, "An {}", self.anno)?;
+                if self.anno.is_before_era() {
+                    write!(f, " {}", self.anno.era_abbreviation())?;
+                }
+            }
+            Style::Ascii => {
+                write!(f, "Sun {} : Moon {} : ", self.sun, self.moon)?;
+                for planet in &self.planets {
+                    write!(f, "{} {} : ", planet.planet.name(), planet.position)?;
+                }
+                if let Some(moon_phase) = &self.moon_phase {
+                    write!(f, "{} : ", to_ascii(&moon_phase.to_string()))?;
+                }
+                write!(f, "{} : ", to_ascii(self.language.weekday(self.weekday)))?;
+                if let Some(hour) = &self.planetary_hour {
+                    write!(f, "{} : ", hour)?;
+                }
+                write!(f, "{}", to_ascii(&self.language.anno(&self.anno)))?;
+            }
+        }
+        if let Some(houses) = &self.houses {
+            write!(f, "\n{}", houses)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use chrono_tz::{Europe::London, UTC};
+
+    #[test]
+    fn finds_the_vernal_equinox() {
+        // 2025 March 20, 09:01 UT
+        let equinox = ThelemicDate::vernal_equinox(2025, &UTC).unwrap();
+        let expected = UTC.with_ymd_and_hms(2025, 3, 20, 9, 1, 0).unwrap();
+        assert!((equinox - expected).num_seconds().abs() < 60, "{}", equinox);
+
+        // 1904 March 21, 01:00 UT, the Equinox of the Gods
+        let equinox = ThelemicDate::vernal_equinox(1904, &UTC).unwrap();
+        let expected = UTC.with_ymd_and_hms(1904, 3, 21, 0, 58, 0).unwrap();
+        assert!((equinox - expected).num_seconds().abs() < 120, "{}", equinox);
+    }
+
+    #[test]
+    fn rolls_the_anno_over_at_the_equinox() {
+        let equinox = ThelemicDate::vernal_equinox(2025, &London).unwrap();
+        let before = ThelemicDate::anno(&(equinox - Duration::seconds(1))).unwrap();
+        let after = ThelemicDate::anno(&equinox).unwrap();
+        assert_eq!(before.to_string(), "Vx");
+        assert_eq!(after.to_string(), "Vxi");
+
+        let dt = London.with_ymd_and_hms(1904, 1, 1, 0, 0, 0).unwrap();
+        assert!(ThelemicDate::anno(&dt).unwrap().is_before_era());
+        assert!(ThelemicDate::vernal_equinox(40_000, &UTC).is_err());
+    }
+
+    #[test]
+    fn converts_julian_days() {
+        let j2000 = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(ThelemicDate::julian_day(&j2000), 2_451_545.0);
+        assert_eq!(ThelemicDate::utc_from_julian_day(2_451_545.0).unwrap().naive_utc(), j2000);
+    }
+
+    #[test]
+    fn warns_of_expired_locations_used_offline() {
+        let path = std::env::temp_dir().join(format!("tdate-stale-{}", std::process::id())).join("locations.json");
+        let cache = LocationCache::new(path.clone(), std::time::Duration::ZERO);
+        let entry = CachedLocation {
+            latitude: 40.0,
+            longitude: 116.0,
+            timezone: "Asia/Shanghai".to_string(),
+            source: Nominatim::new(Nominatim::DEFAULT_ENDPOINT).source(),
+            resolved_at: 0,
+        };
+        let entries = std::collections::BTreeMap::from([("xanadu", entry)]);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, serde_json::to_string(&entries).unwrap()).unwrap();
+
+        let date_data = ThelemicDate::new().with_cache(Some(cache)).with_offline(true);
+        let location = Location::Place("Xanadu".to_string());
+        for _ in 0..2 {
+            let resolved = date_data.resolve(&location).unwrap();
+            assert_eq!(resolved.coordinates(), Some((40.0, 116.0)));
+        }
+        assert_eq!(date_data.take_warnings(), ["Using an expired cached location for Xanadu (offline)"]);
+        assert!(date_data.take_warnings().is_empty());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,81 @@
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+/// Where a date is observed from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    /// A place name, resolved through the gazetteer or OpenStreetMap
+    Place(String),
+    /// Geographic coordinates in degrees. The timezone is looked up from the
+    /// coordinates unless one is given.
+    Coordinates {
+        latitude: f64,
+        longitude: f64,
+        timezone: Option<Tz>,
+    },
+    /// A timezone alone, for when only the local time matters
+    Timezone(Tz),
+}
+
+impl Location {
+    /// Builds a `Location::Coordinates`, checking that both values lie on the globe.
+    pub fn coordinates(latitude: f64, longitude: f64, timezone: Option<Tz>)
+        -> Result<Self, Box<dyn std::error::Error>> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(format!("Latitude {} is out of range (-90 to 90)", latitude).into());
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(format!("Longitude {} is out of range (-180 to 180)", longitude).into());
+        }
+        Ok(Location::Coordinates { latitude, longitude, timezone })
+    }
+
+    /// Parses coordinates written as "LAT,LON" in decimal degrees, e.g.
+    /// "51.5074,-0.1278".
+    pub fn parse_coordinates(s: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let (lat, lon) = s.split_once(',')
+            .ok_or_else(|| format!("Expected coordinates as LAT,LON: {}", s))?;
+        let latitude = lat.trim().parse::<f64>()
+            .map_err(|_| format!("Invalid latitude: {}", lat.trim()))?;
+        let longitude = lon.trim().parse::<f64>()
+            .map_err(|_| format!("Invalid longitude: {}", lon.trim()))?;
+        Self::coordinates(latitude, longitude, None)
+    }
+
+    /// Parses an IANA timezone name such as "Europe/London".
+    pub fn parse_timezone(s: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let tz = Tz::from_str(s.trim())
+            .map_err(|e| format!("Invalid timezone: {}", e))?;
+        Ok(Location::Timezone(tz))
+    }
+}
+
+impl From<&str> for Location {
+    fn from(place: &str) -> Self {
+        Location::Place(place.to_string())
+    }
+}
+
+impl From<String> for Location {
+    fn from(place: String) -> Self {
+        Location::Place(place)
+    }
+}
+
+/// A location after geocoding: the timezone dates are reckoned in, and the
+/// coordinates when they are known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLocation {
+    /// Latitude in degrees, absent when only a timezone was given
+    pub latitude: Option<f64>,
+    /// Longitude in degrees, absent when only a timezone was given
+    pub longitude: Option<f64>,
+    pub timezone: Tz,
+}
+
+impl ResolvedLocation {
+    /// Latitude and longitude in degrees, when both are known.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        Some((self.latitude?, self.longitude?))
+    }
+}
@@ -1,217 +1,231 @@
-use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, Timelike, Weekday, TimeZone};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
 use chrono_tz::Tz;
-use geocoding::{Openstreetmap, Point, Forward};
-use tzf_rs::DefaultFinder;
-use astro::{sun, lunar, time};
-use std::f64::consts::PI;
-use clap::Parser;
+use tdate::{parse_geocoder, parse_step, write_ics, Anno, Ayanamsa, DegreeFormat, EphemerisFormat, HouseSystem, Language, Location, Luminary, OutputFormat, Planet, Precision, Preset, Style, Template, ThelemicDate, ThelemicDateQuery, ThelemicDateValue, Zodiac};
 
 #[derive(Parser)]
 #[command(name = "tdate")]
 #[command(version = "0.1.1\n93 93/93\nDo what thou wilt shall be the whole of the Law.\nLove is the law, love under will.\n\nThanks to Lilith Vala Xara for the original implementation\nand JSKitty for the Rust port.")]
 #[command(about = "Displays the current Thelemic date", long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Location for date calculation (e.g., "Las Vegas, NV")
-    #[arg(short, long)]
+    #[arg(short, long, global = true, conflicts_with_all = ["coords", "tz"])]
     location: Option<String>,
+
+    /// Coordinates for date calculation, in decimal degrees (e.g., "51.5074,-0.1278")
+    #[arg(long, global = true, value_name = "LAT,LON", allow_hyphen_values = true)]
+    coords: Option<String>,
+
+    /// IANA timezone for date calculation (e.g., "Europe/London"); overrides the
+    /// timezone found from --coords
+    #[arg(long, global = true, value_name = "TIMEZONE")]
+    tz: Option<String>,
+
+    /// Resolve locations from the built-in city list only, without network access
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Geocoding provider, asked before the built-in city list: "nominatim",
+    /// "nominatim=URL", "photon", "photon=URL" or "file=PATH" (default:
+    /// Nominatim, for places missing from the built-in city list)
+    #[arg(long, global = true, env = "TDATE_GEOCODER", value_name = "PROVIDER")]
+    geocoder: Option<String>,
+
+    /// Geocode the location again instead of using the cached result
+    #[arg(long, global = true)]
+    refresh_location: bool,
+
+    /// Also show the positions of Mercury through Pluto
+    #[arg(long, global = true)]
+    planets: bool,
+
+    /// Zodiac to measure positions in: "tropical" (default) or "sidereal"
+    #[arg(long, global = true, default_value = "tropical")]
+    zodiac: String,
+
+    /// Ayanamsa for the sidereal zodiac: "lahiri" (default), "fagan-bradley",
+    /// "krishnamurti", "raman" or "yukteshwar"
+    #[arg(long, global = true)]
+    ayanamsa: Option<String>,
+
+    /// Language of the date line: "en", "la", "es", "pt", "de" or "fr"
+    /// (default: from the LANG environment variable, else English)
+    #[arg(long, global = true)]
+    lang: Option<String>,
+
+    /// Style of the date line: "text", "glyph" for glyphs throughout or
+    /// "ascii" for plain ASCII (default: the preset's, else text)
+    #[arg(long, global = true)]
+    style: Option<String>,
+
+    /// Report geometric positions, without the corrections for ΔT, nutation
+    /// and aberration that give the apparent ones
+    #[arg(long, global = true)]
+    geometric: bool,
+
+    /// Precision of degrees within a sign: "whole" (default), "minutes",
+    /// "seconds", "decimal" or "decimal:N" for N places
+    #[arg(long, global = true, value_name = "PRECISION", default_value = "whole")]
+    degrees: String,
+
+    /// Round degrees to the precision shown instead of truncating them
+    #[arg(long, global = true)]
+    round: bool,
+
+    /// Count whole degrees within a sign from 1 to 30 instead of 0 to 29
+    #[arg(long, global = true)]
+    ordinal: bool,
+
+    /// Also show the ruler of the current planetary hour
+    #[arg(long, global = true)]
+    hour: bool,
+
+    /// Start each weekday at local sunrise rather than midnight
+    #[arg(long, global = true)]
+    sunrise_day: bool,
+
+    /// Also show the Moon's phase, illumination and age, with the phase named
+    /// in the date line's language unless another is given, e.g. "latin"
+    #[arg(long, global = true, value_name = "LANG", num_args = 0..=1, require_equals = true)]
+    phase: Option<Option<String>>,
+
+    /// Also show the Ascendant, Midheaven, local sidereal time and house cusps:
+    /// "placidus" (default), "whole-sign", "equal" or "koch"
+    #[arg(long, global = true, value_name = "SYSTEM", num_args = 0..=1,
+          require_equals = true, default_missing_value = "placidus")]
+    houses: Option<String>,
     
-    /// Date and time in format: year month day hour minute location
-    #[arg(num_args = 6, value_names = &["YEAR", "MONTH", "DAY", "HOUR", "MINUTE", "LOCATION"])]
+    /// Date and time in format: year month day hour minute location (the
+    /// location may be left out when given by -l, --coords or --tz, and
+    /// cannot be combined with them)
+    #[arg(num_args = 5..=6, value_names = &["YEAR", "MONTH", "DAY", "HOUR", "MINUTE", "LOCATION"])]
     datetime: Option<Vec<String>>,
     
+    /// Output format: "text" (the date line), "json", "toml" or "kv" (key=value lines)
+    #[arg(long, default_value = "text")]
+    format: String,
+
+    /// Layout for the date line in place of the usual one, e.g.
+    /// "{sun.glyph} {sun.deg}° {sun.sign} | dies {weekday} | An {anno}"; see the README
+    #[arg(long, conflicts_with = "format")]
+    template: Option<String>,
+
+    /// Named layout for the date line: "traditional", "traditional-en",
+    /// "traditional-ev", "heading", "heading-en" or "heading-ev"; see the README
+    #[arg(long, conflicts_with_all = ["format", "template"])]
+    preset: Option<String>,
+
     /// Hidden flag for Liber OZ
     #[arg(long = "oz", hide = true)]
     oz: bool,
 }
 
-struct ThelemicDate {
-    finder: DefaultFinder,
-}
-
-impl ThelemicDate {
-    fn new() -> Self {
-        ThelemicDate {
-            finder: DefaultFinder::new(),
-        }
-    }
-
-    const NUMERALS: [&'static str; 23] = [
-        "0", "i", "ii", "iii", "iv",
-        "v", "vi", "vii", "viii", "ix",
-        "x", "xi", "xii", "xiii", "xiv",
-        "xv", "xvi", "xvii", "xviii", "xix",
-        "xx", "xxi", "xxii"
-    ];
-
-    const SIGNS: [(&'static str, &'static str); 12] = [
-        ("Aries", "♈"), ("Taurus", "♉"), ("Gemini", "♊"), ("Cancer", "♋"),
-        ("Leo", "♌"), ("Virgo", "♍"), ("Libra", "♎"), ("Scorpio", "♏"),
-        ("Sagittarius", "♐"), ("Capricorn", "♑"), ("Aquarius", "♒"), ("Pisces", "♓")
-    ];
-
-    const DAYS_OF_WEEK: [&'static str; 7] = [
-        "Lunae", "Martis", "Mercurii", "Jovis",
-        "Veneris", "Saturnii", "Solis"
-    ];
-
-    fn get_geopos(&self, location: &str) -> Result<(f64, f64, String), Box<dyn std::error::Error>> {
-        let osm = Openstreetmap::new();
-        let res: Vec<Point<f64>> = osm.forward(location)?;
-        
-        if let Some(point) = res.first() {
-            let lat = point.y();
-            let lon = point.x();
-            let timezone_name = self.finder.get_tz_name(lon, lat);
-            Ok((lat, lon, timezone_name.to_string()))
-        } else {
-            Err("Location not found".into())
-        }
-    }
-
-    fn get_timezone(&self, location: &str) -> Result<Tz, Box<dyn std::error::Error>> {
-        let (_, _, tz_name) = self.get_geopos(location)?;
-        tz_name.parse::<Tz>()
-            .map_err(|e| format!("Invalid timezone: {}", e).into())
-    }
-
-    fn get_sign_from_longitude(&self, longitude: f64) -> &'static str {
-        let degree = longitude * 180.0 / PI;
-        let normalized_degree = if degree < 0.0 { degree + 360.0 } else { degree };
-        let sign_index = (normalized_degree / 30.0) as usize;
-        Self::SIGNS[sign_index % 12].0
-    }
-
-    fn get_degree_in_sign(&self, longitude: f64) -> i32 {
-        let degree = longitude * 180.0 / PI;
-        let normalized_degree = if degree < 0.0 { degree + 360.0 } else { degree };
-        (normalized_degree % 30.0) as i32
-    }
+impl Cli {
+    /// Builds the observer's location from the location flags, falling back to
+    /// `place` (the LOCATION argument of the datetime mode) and then the default.
+    fn location(&self, place: Option<&str>) -> Result<Location, Box<dyn std::error::Error>> {
+        let tz = match self.tz.as_deref().map(Location::parse_timezone).transpose()? {
+            Some(Location::Timezone(tz)) => Some(tz),
+            _ => None,
+        };
 
-    fn weekday_to_index(weekday: Weekday) -> usize {
-        match weekday {
-            Weekday::Mon => 0,
-            Weekday::Tue => 1,
-            Weekday::Wed => 2,
-            Weekday::Thu => 3,
-            Weekday::Fri => 4,
-            Weekday::Sat => 5,
-            Weekday::Sun => 6,
+        match (&self.coords, tz) {
+            (Some(coords), tz) => {
+                let mut location = Location::parse_coordinates(coords)?;
+                if let Location::Coordinates { timezone, .. } = &mut location {
+                    *timezone = tz;
+                }
+                Ok(location)
+            }
+            (None, Some(tz)) => Ok(Location::Timezone(tz)),
+            (None, None) => Ok(place.or(self.location.as_deref()).unwrap_or("Las Vegas, NV").into()),
         }
     }
+}
 
-    pub fn now(&self, location: &str) -> Result<String, Box<dyn std::error::Error>> {
-        let (_lat, _lon, _tz_name) = self.get_geopos(location)?;
-        let tz = self.get_timezone(location)?;
-        let now = Local::now().with_timezone(&tz);
-        
-        let ve_year = now.year();
-        let ve_years_total = ve_year - 1904;
-        let cycle_i = ve_years_total / 22;
-        let cycle_ii = ve_year - 1904 - (cycle_i * 22);
-        let na_year = format!("{}{}", 
-            Self::NUMERALS[cycle_i as usize].to_uppercase(), 
-            Self::NUMERALS[cycle_ii as usize]
-        );
-        
-        let ve_weekday = Self::weekday_to_index(now.weekday());
-        
-        // Calculate Julian Day
-        let naive_utc = now.naive_utc();
-        let jd = time::julian_day(
-            &time::Date {
-                year: naive_utc.year() as i16,
-                month: naive_utc.month() as u8,
-                decimal_day: naive_utc.day() as f64 
-                    + naive_utc.hour() as f64 / 24.0 
-                    + naive_utc.minute() as f64 / 1440.0 
-                    + naive_utc.second() as f64 / 86400.0,
-                cal_type: time::CalType::Gregorian,
-            }
-        );
-        
-        // Get sun position
-        let (sun_pos, _sun_dist) = sun::geocent_ecl_pos(jd);
-        let sun_sign = self.get_sign_from_longitude(sun_pos.long);
-        let sun_degree = self.get_degree_in_sign(sun_pos.long);
-        
-        // Get moon position
-        let (moon_pos, _moon_dist) = lunar::geocent_ecl_pos(jd);
-        let moon_sign = self.get_sign_from_longitude(moon_pos.long);
-        let moon_degree = self.get_degree_in_sign(moon_pos.long);
-        
-        Ok(format!(
-            "☉ in {}º {} : ☽ in {}º {} : dies {} : Anno {} æræ legis",
-            sun_degree, sun_sign,
-            moon_degree, moon_sign,
-            Self::DAYS_OF_WEEK[ve_weekday],
-            na_year
-        ))
-    }
+#[derive(Subcommand)]
+enum Command {
+    /// Finds when a Thelemic date held, as Gregorian time windows
+    Parse {
+        /// Thelemic date (e.g., "☉ in 1º Leo : ☽ in 16º Cancer : dies Mercurii : Anno Vxi æræ legis")
+        date: String,
+    },
+    /// Shows the times of the four adorations of Liber Resh for a day
+    Resh {
+        /// Day to show, as YYYY-MM-DD (default: today)
+        date: Option<String>,
+    },
+    /// Lists the 24 planetary hours of the day beginning at sunrise on a date
+    Hours {
+        /// Day to show, as YYYY-MM-DD (default: today)
+        date: Option<String>,
+    },
+    /// Finds when the Sun and Moon next enter a new sign, or every such
+    /// ingress within a range
+    Ingress {
+        /// Start of the search, as YYYY-MM-DD or "YYYY-MM-DD HH:MM" local time (default: now)
+        #[arg(long)]
+        from: Option<String>,
+        /// End of the search, as YYYY-MM-DD (through the end of that day) or
+        /// "YYYY-MM-DD HH:MM"; without it only the next ingress of each body is shown
+        #[arg(long)]
+        to: Option<String>,
+        /// Only look at one body: "sun" or "moon"
+        #[arg(long)]
+        body: Option<String>,
+    },
+    /// Writes the Thelemic date at regular steps over a range, as CSV, JSON or
+    /// JSON Lines
+    Range {
+        /// Start of the range, as YYYY-MM-DD or "YYYY-MM-DD HH:MM" local time
+        #[arg(long)]
+        from: String,
+        /// End of the range (exclusive), as YYYY-MM-DD or "YYYY-MM-DD HH:MM" local time
+        #[arg(long)]
+        to: String,
+        /// Time between rows, e.g. 1d, 6h, 30m
+        #[arg(long, default_value = "1d")]
+        step: String,
+        /// Output format: "csv", "json" or "jsonl"
+        #[arg(long, default_value = "csv")]
+        output: String,
+    },
+    /// Lists the Thelemic holy days of an Anno, with their Thelemic dates
+    Feasts {
+        /// Anno to list, e.g. "Vxi" (default: the current one)
+        #[arg(long)]
+        anno: Option<String>,
+    },
+    /// Writes an iCalendar file of the feasts, Sun ingresses and new and full
+    /// moons of an Anno or a Gregorian range
+    Ics {
+        /// Anno to cover, e.g. "Vxi" (default: the current one)
+        #[arg(long, conflicts_with_all = ["from", "to"])]
+        anno: Option<String>,
+        /// Start of a Gregorian range, as YYYY-MM-DD or "YYYY-MM-DD HH:MM" local time
+        #[arg(long, requires = "to")]
+        from: Option<String>,
+        /// End of the range, as YYYY-MM-DD (through the end of that day) or
+        /// "YYYY-MM-DD HH:MM"
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+    },
+    /// Lists or clears cached geocoding results
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
 
-    pub fn in_day(&self, year: i32, month: u32, day: u32, hour: u32, minute: u32, location: &str) 
-        -> Result<String, Box<dyn std::error::Error>> {
-        let (_lat, _lon, _tz_name) = self.get_geopos(location)?;
-        let tz = self.get_timezone(location)?;
-        
-        let date = NaiveDate::from_ymd_opt(year, month, day)
-            .ok_or("Invalid date")?;
-        let ve_weekday = Self::weekday_to_index(date.weekday());
-        
-        // Adjust year for Thelemic calendar (New Year starts at spring equinox ~March 20)
-        let ve_in_day_na_year = if month < 3 || (month == 3 && day < 20) {
-            year - 1905
-        } else {
-            year - 1904
-        };
-        
-        let cycle_i = ve_in_day_na_year / 22;
-        let cycle_ii = ve_in_day_na_year - (cycle_i * 22);
-        let na_year = format!("{}{}", 
-            Self::NUMERALS[cycle_i as usize].to_uppercase(), 
-            Self::NUMERALS[cycle_ii as usize]
-        );
-        
-        // Create datetime
-        let naive_dt = NaiveDateTime::new(
-            date,
-            chrono::NaiveTime::from_hms_opt(hour, minute, 0).ok_or("Invalid time")?
-        );
-        let dt = tz.from_local_datetime(&naive_dt)
-            .single()
-            .ok_or("Ambiguous local time")?;
-        
-        // Calculate Julian Day
-        let naive_utc = dt.naive_utc();
-        let jd = time::julian_day(
-            &time::Date {
-                year: naive_utc.year() as i16,
-                month: naive_utc.month() as u8,
-                decimal_day: naive_utc.day() as f64 
-                    + naive_utc.hour() as f64 / 24.0 
-                    + naive_utc.minute() as f64 / 1440.0 
-                    + naive_utc.second() as f64 / 86400.0,
-                cal_type: time::CalType::Gregorian,
-            }
-        );
-        
-        // Get sun position
-        let (sun_pos, _sun_dist) = sun::geocent_ecl_pos(jd);
-        let sun_sign = self.get_sign_from_longitude(sun_pos.long);
-        let sun_degree = self.get_degree_in_sign(sun_pos.long);
-        
-        // Get moon position
-        let (moon_pos, _moon_dist) = lunar::geocent_ecl_pos(jd);
-        let moon_sign = self.get_sign_from_longitude(moon_pos.long);
-        let moon_degree = self.get_degree_in_sign(moon_pos.long);
-        
-        Ok(format!(
-            "☉ in {}º {} : ☽ in {}º {} : dies {} : Anno {} æræ legis",
-            sun_degree, sun_sign,
-            moon_degree, moon_sign,
-            Self::DAYS_OF_WEEK[ve_weekday],
-            na_year
-        ))
-    }
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Lists cached locations
+    List,
+    /// Removes every cached location
+    Clear,
 }
 
 fn print_liber_oz() {
@@ -263,6 +277,200 @@ There is no god but man.
 "#);
 }
 
+/// Builds the zodiac from the --zodiac and --ayanamsa flags.
+fn zodiac(zodiac: &str, ayanamsa: Option<&str>) -> Result<Zodiac, Box<dyn std::error::Error>> {
+    match zodiac.trim().to_lowercase().as_str() {
+        "tropical" if ayanamsa.is_some() => Err("--ayanamsa only applies with --zodiac sidereal".into()),
+        "tropical" => Ok(Zodiac::Tropical),
+        "sidereal" => Ok(Zodiac::Sidereal(ayanamsa.unwrap_or("lahiri").parse::<Ayanamsa>()?)),
+        _ => Err(format!("Unknown zodiac: {} (expected tropical or sidereal)", zodiac).into()),
+    }
+}
+
+/// Reads a YYYY-MM-DD date argument, defaulting to today in `tz`.
+fn parse_day(date: Option<&str>, tz: &Tz) -> Result<NaiveDate, Box<dyn std::error::Error>> {
+    match date {
+        Some(date) => Ok(NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date (expected YYYY-MM-DD): {}", date))?),
+        None => Ok(Utc::now().with_timezone(tz).date_naive()),
+    }
+}
+
+/// Reads a local date and time as YYYY-MM-DD, "YYYY-MM-DD HH:MM" or
+/// YYYY-MM-DDTHH:MM. A bare date stands for the start of the day, or for its
+/// end when `end_of_day` is set.
+fn parse_local(s: &str, tz: &Tz, end_of_day: bool) -> Result<DateTime<Tz>, Box<dyn std::error::Error>> {
+    let s = s.trim();
+    let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| NaiveDate::parse_from_str(s, "%Y-%m-%d").map(|date| {
+            let date = if end_of_day { date + Duration::days(1) } else { date };
+            date.and_time(NaiveTime::MIN)
+        }))
+        .map_err(|_| format!("Invalid date (expected YYYY-MM-DD or \"YYYY-MM-DD HH:MM\"): {}", s))?;
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| format!("Invalid local time: {}", s).into())
+}
+
+/// Writes an ephemeris over a range to stdout.
+fn print_range(date_data: &ThelemicDate, from: &str, to: &str, step: &str, output: &str, location: &Location)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let format = output.parse::<EphemerisFormat>()?;
+    let step = parse_step(step)?;
+    let resolved = date_data.resolve(location)?;
+    let tz = resolved.timezone;
+    let rows = date_data.ephemeris(&parse_local(from, &tz, false)?, &parse_local(to, &tz, false)?, step, &resolved)?;
+    format.write(&rows, &mut std::io::stdout().lock())?;
+    Ok(())
+}
+
+/// Prints the next ingress of each body, or every ingress within a range.
+fn print_ingresses(date_data: &ThelemicDate, from: Option<&str>, to: Option<&str>, body: Option<&str>, location: &Location)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let tz = date_data.resolve(location)?.timezone;
+    let from = match from {
+        Some(from) => parse_local(from, &tz, false)?,
+        None => Utc::now().with_timezone(&tz),
+    };
+    let bodies = match body {
+        Some(body) => vec![body.parse::<Luminary>()?],
+        None => Luminary::ALL.to_vec(),
+    };
+
+    let mut ingresses: Vec<_> = match to {
+        Some(to) => date_data.ingresses(&bodies, &from, &parse_local(to, &tz, true)?)?,
+        None => bodies.iter()
+            .map(|body| date_data.next_ingress(*body, &from))
+            .collect::<Result<_, _>>()?,
+    };
+    ingresses.sort_by_key(|ingress| ingress.time);
+    for ingress in ingresses {
+        println!("{} {} {}", ingress.time.format("%Y-%m-%d"), ingress, ingress.time.format("%Z"));
+    }
+    Ok(())
+}
+
+/// Notes on stderr any planet that was asked for but left out of a date
+/// because its theory does not reach it (Pluto outside 1885–2099).
+fn note_missing_planets(value: &ThelemicDateValue) {
+    if value.planets.is_empty() {
+        return;
+    }
+    for planet in Planet::ALL {
+        if !value.planets.iter().any(|position| position.planet == planet) {
+            eprintln!("Note: {} is only computed for 1885–2099 and is left out", planet.name());
+        }
+    }
+}
+
+/// Prints on stderr the warnings the library raised along the way, such as
+/// an expired cached location used offline.
+fn note_warnings(date_data: &ThelemicDate) {
+    for warning in date_data.take_warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+/// Prints the feasts of an Anno, each followed by its Thelemic date.
+fn print_feasts(date_data: &ThelemicDate, anno: Option<&str>, location: &Location)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let resolved = date_data.resolve(location)?;
+    let tz = resolved.timezone;
+    let anno = match anno {
+        Some(anno) => anno.parse::<Anno>()?,
+        None => ThelemicDate::anno(&Utc::now().with_timezone(&tz))?,
+    };
+
+    for feast in ThelemicDate::feasts(anno, &tz)? {
+        let when = match feast.time {
+            Some(time) => time.format("%a %Y-%m-%d %H:%M %Z").to_string(),
+            None => feast.date.format("%a %Y-%m-%d").to_string(),
+        };
+        println!("{:<24} {}", when, feast.observance);
+        println!("    {}", date_data.at_location(&feast.moment(&tz)?, &resolved)?);
+    }
+    Ok(())
+}
+
+/// Writes an iCalendar file covering an Anno or a Gregorian range to stdout.
+fn write_calendar(date_data: &ThelemicDate, anno: Option<&str>, range: Option<(&str, &str)>, location: &Location)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let resolved = date_data.resolve(location)?;
+    let tz = resolved.timezone;
+    let (from, to) = match range {
+        Some((from, to)) => (parse_local(from, &tz, false)?, parse_local(to, &tz, true)?),
+        None => {
+            let anno = match anno {
+                Some(anno) => anno.parse::<Anno>()?,
+                None => ThelemicDate::anno(&Utc::now().with_timezone(&tz))?,
+            };
+            // An Anno's calendar opens on the day of the Supreme Ritual, or of
+            // the equinox when that falls earlier
+            let opening = |year: i32| -> Result<DateTime<Tz>, Box<dyn std::error::Error>> {
+                let equinox = ThelemicDate::vernal_equinox(year, &tz)?.date_naive();
+                let ritual = NaiveDate::from_ymd_opt(year, 3, 20).ok_or("Date out of range")?;
+                parse_local(&equinox.min(ritual).format("%Y-%m-%d").to_string(), &tz, false)
+            };
+            let year = anno.gregorian_year();
+            (opening(year)?, opening(year + 1)?)
+        }
+    };
+
+    let events = date_data.calendar_events(&from, &to, &resolved)?;
+    write_ics(&events, &tz, &mut std::io::stdout().lock())?;
+    Ok(())
+}
+
+/// Prints the planetary hours of a day, marking the current one.
+fn print_hours(date_data: &ThelemicDate, date: Option<&str>, location: &Location)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let tz = date_data.resolve(location)?.timezone;
+    let now = Utc::now().with_timezone(&tz);
+    let day = parse_day(date, &tz)?;
+
+    let hours = date_data.planetary_hours(day, location)?;
+    println!("{}", hours[0].weekday());
+    let width = hours.iter().map(|hour| hour.to_string().chars().count()).max().unwrap_or(0);
+    for hour in hours {
+        println!("{}{:>2} {:<width$} {} {} {}",
+            if hour.start <= now && now < hour.end { "*" } else { " " },
+            hour.number,
+            hour.to_string(),
+            hour.start.format("%H:%M"),
+            if hour.style == Style::Ascii { "-" } else { "–" },
+            hour.end.format("%H:%M %Z")
+        );
+    }
+    Ok(())
+}
+
+/// Prints the adoration times of a day, and the next adoration when the day
+/// is today.
+fn print_resh(date_data: &ThelemicDate, date: Option<&str>, location: &Location)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let tz = date_data.resolve(location)?.timezone;
+    let now = Utc::now().with_timezone(&tz);
+    let day = parse_day(date, &tz)?;
+
+    let resh = date_data.resh(day, location)?;
+    for (adoration, time) in resh.adorations() {
+        let time = match time {
+            Some(time) if time.date_naive() == day => time.format("%H:%M %Z").to_string(),
+            Some(time) => time.format("%H:%M %Z (%Y-%m-%d)").to_string(),
+            None => "none".to_string(),
+        };
+        println!("{:<9}{:<15}{}", adoration.name(), adoration.event(), time);
+    }
+
+    if day == now.date_naive() {
+        let (adoration, time) = date_data.next_adoration(&now, location)?;
+        let minutes = (time - now).num_minutes();
+        println!("Next: {} at {} (in {}h {:02}m)", adoration.name(), time.format("%H:%M"), minutes / 60, minutes % 60);
+    }
+    Ok(())
+}
+
 fn main() {
     let cli = Cli::parse();
     
@@ -272,11 +480,197 @@ fn main() {
         return;
     }
     
-    let date_data = ThelemicDate::new();
+    let mut date_data = ThelemicDate::new()
+        .with_offline(cli.offline)
+        .with_refresh_location(cli.refresh_location)
+        .with_planets(cli.planets)
+        .with_geometric(cli.geometric)
+        .with_planetary_hour(cli.hour)
+        .with_sunrise_day(cli.sunrise_day);
+    match zodiac(&cli.zodiac, cli.ayanamsa.as_deref()) {
+        Ok(zodiac) => date_data = date_data.with_zodiac(zodiac),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+    let language = match &cli.lang {
+        Some(lang) => lang.parse::<Language>(),
+        None => Ok(std::env::var("LANG").map(|locale| Language::from_locale(&locale)).unwrap_or_default()),
+    };
+    let language = match language {
+        Ok(language) => language,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    date_data = date_data.with_language(language);
+    let preset = match cli.preset.as_deref().map(str::parse::<Preset>).transpose() {
+        Ok(preset) => preset,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let style = match &cli.style {
+        Some(style) => style.parse::<Style>(),
+        None => Ok(preset.map(|preset| preset.style()).unwrap_or_default()),
+    };
+    match style {
+        Ok(style) => date_data = date_data.with_style(style),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+    match cli.degrees.parse::<Precision>() {
+        Ok(precision) if cli.ordinal && precision != Precision::Whole => {
+            eprintln!("Error: --ordinal counts whole degrees only and cannot be combined with --degrees {}", cli.degrees);
+            std::process::exit(1);
+        }
+        Ok(precision) => {
+            date_data = date_data.with_degree_format(DegreeFormat { precision, round: cli.round, ordinal: cli.ordinal })
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+    if let Some(system) = &cli.houses {
+        match system.parse::<HouseSystem>() {
+            Ok(system) => date_data = date_data.with_houses(Some(system)),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(names) = &cli.phase {
+        match names.as_deref().map(str::parse::<Language>).transpose() {
+            Ok(names) => date_data = date_data.with_moon_phase(Some(names.unwrap_or(language))),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(spec) = &cli.geocoder {
+        match parse_geocoder(spec) {
+            Ok(geocoder) => date_data = date_data.with_geocoder(geocoder),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
     
-    if let Some(datetime_args) = cli.datetime {
+    let format = match cli.format.parse::<OutputFormat>() {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let template = match cli.template.as_deref().map(str::parse::<Template>).transpose() {
+        Ok(template) => template.or_else(|| preset.map(|preset| preset.template())),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let render = |value: ThelemicDateValue| match &template {
+        Some(template) => Ok(template.render(&value)),
+        None => format.render(&value),
+    };
+    
+    if let Some(command) = &cli.command {
+        let location = match cli.location(None) {
+            Ok(location) => location,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        match command {
+            Command::Parse { date } => {
+                let windows = date.parse::<ThelemicDateQuery>()
+                    .and_then(|query| date_data.find(&query, &location));
+                match windows {
+                    Ok(windows) if windows.is_empty() => eprintln!("Error: No matching date found"),
+                    Ok(windows) => {
+                        for window in windows {
+                            println!("{} – {}",
+                                window.start.format("%Y-%m-%d %H:%M %Z"),
+                                window.end.format("%Y-%m-%d %H:%M %Z")
+                            );
+                        }
+                    }
+                    Err(e) => eprintln!("Error: {}", e),
+                }
+            }
+            Command::Resh { date } => {
+                if let Err(e) = print_resh(&date_data, date.as_deref(), &location) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            Command::Hours { date } => {
+                if let Err(e) = print_hours(&date_data, date.as_deref(), &location) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            Command::Ingress { from, to, body } => {
+                if let Err(e) = print_ingresses(&date_data, from.as_deref(), to.as_deref(), body.as_deref(), &location) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            Command::Range { from, to, step, output } => {
+                if let Err(e) = print_range(&date_data, from, to, step, output, &location) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            Command::Feasts { anno } => {
+                if let Err(e) = print_feasts(&date_data, anno.as_deref(), &location) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            Command::Ics { anno, from, to } => {
+                if let Err(e) = write_calendar(&date_data, anno.as_deref(), from.as_deref().zip(to.as_deref()), &location) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            Command::Cache { action } => {
+                let Some(cache) = date_data.cache() else {
+                    eprintln!("Error: No cache directory available");
+                    std::process::exit(1);
+                };
+                match action {
+                    CacheAction::List => {
+                        for (query, entry) in cache.entries() {
+                            println!("{}: {:.4},{:.4} ({}) via {}{}",
+                                query, entry.latitude, entry.longitude, entry.timezone, entry.source,
+                                if cache.is_expired(&entry) { " [expired]" } else { "" }
+                            );
+                        }
+                    }
+                    CacheAction::Clear => match cache.clear() {
+                        Ok(count) => println!("Removed {} cached location(s) from {}", count, cache.path().display()),
+                        Err(e) => eprintln!("Error: {}", e),
+                    },
+                }
+            }
+        }
+        note_warnings(&date_data);
+    } else if let Some(datetime_args) = &cli.datetime {
         // Handle specific date/time
-        if datetime_args.len() != 6 {
+        let has_location_flag = cli.location.is_some() || cli.coords.is_some() || cli.tz.is_some();
+        if datetime_args.len() == 6 && has_location_flag {
+            Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                "the LOCATION argument cannot be used with '--location', '--coords' or '--tz'",
+            ).exit();
+        }
+        if datetime_args.len() != 6 && !has_location_flag {
             eprintln!("Error: Expected 6 arguments for datetime (year month day hour minute location)");
             std::process::exit(1);
         }
@@ -286,16 +680,24 @@ fn main() {
         let day: u32 = datetime_args[2].parse().expect("Invalid day");
         let hour: u32 = datetime_args[3].parse().expect("Invalid hour");
         let minute: u32 = datetime_args[4].parse().expect("Invalid minute");
-        let location = &datetime_args[5];
         
-        match date_data.in_day(year, month, day, hour, minute, location) {
+        let result = cli.location(datetime_args.get(5).map(String::as_str))
+            .and_then(|location| date_data.in_day(year, month, day, hour, minute, &location))
+            .inspect(note_missing_planets)
+            .and_then(render);
+        note_warnings(&date_data);
+        match result {
             Ok(result) => println!("{}", result),
             Err(e) => eprintln!("Error: {}", e),
         }
     } else {
         // Handle current date/time
-        let location = cli.location.as_deref().unwrap_or("Las Vegas, NV");
-        match date_data.now(location) {
+        let result = cli.location(None)
+            .and_then(|location| date_data.now(&location))
+            .inspect(note_missing_planets)
+            .and_then(render);
+        note_warnings(&date_data);
+        match result {
             Ok(current_date) => println!("{}", current_date),
             Err(e) => eprintln!("Error: {}", e),
         }
@@ -0,0 +1,278 @@
+use astro::{lunar, sun};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::fmt;
+
+use crate::{Language, ThelemicDate};
+
+/// Mean length of the lunar month, in days.
+const SYNODIC_MONTH: f64 = 29.530_589;
+
+/// Kilometres in an astronomical unit.
+const AU_KM: f64 = 149_597_870.7;
+
+/// Elongation either side of a principal phase (new, quarters, full) within
+/// which it is named as such, in degrees; about twelve hours of the Moon's
+/// motion relative to the Sun.
+const PRINCIPAL_ORB: f64 = 6.1;
+
+/// The eight named phases of the Moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum LunarPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl LunarPhase {
+    /// Names the phase for an elongation of the Moon from the Sun in degrees.
+    pub fn from_elongation(elongation: f64) -> Self {
+        let elongation = elongation.rem_euclid(360.0);
+        let near = |target: f64| ((elongation - target + 180.0).rem_euclid(360.0) - 180.0).abs() < PRINCIPAL_ORB;
+        if near(0.0) {
+            LunarPhase::New
+        } else if near(90.0) {
+            LunarPhase::FirstQuarter
+        } else if near(180.0) {
+            LunarPhase::Full
+        } else if near(270.0) {
+            LunarPhase::LastQuarter
+        } else if elongation < 90.0 {
+            LunarPhase::WaxingCrescent
+        } else if elongation < 180.0 {
+            LunarPhase::WaxingGibbous
+        } else if elongation < 270.0 {
+            LunarPhase::WaningGibbous
+        } else {
+            LunarPhase::WaningCrescent
+        }
+    }
+
+    pub fn is_waxing(&self) -> bool {
+        matches!(self, LunarPhase::WaxingCrescent | LunarPhase::FirstQuarter | LunarPhase::WaxingGibbous)
+    }
+
+    pub fn is_waning(&self) -> bool {
+        matches!(self, LunarPhase::WaningGibbous | LunarPhase::LastQuarter | LunarPhase::WaningCrescent)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LunarPhase::New => "New Moon",
+            LunarPhase::WaxingCrescent => "Waxing Crescent",
+            LunarPhase::FirstQuarter => "First Quarter",
+            LunarPhase::WaxingGibbous => "Waxing Gibbous",
+            LunarPhase::Full => "Full Moon",
+            LunarPhase::WaningGibbous => "Waning Gibbous",
+            LunarPhase::LastQuarter => "Last Quarter",
+            LunarPhase::WaningCrescent => "Waning Crescent",
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            LunarPhase::New => "🌑",
+            LunarPhase::WaxingCrescent => "🌒",
+            LunarPhase::FirstQuarter => "🌓",
+            LunarPhase::WaxingGibbous => "🌔",
+            LunarPhase::Full => "🌕",
+            LunarPhase::WaningGibbous => "🌖",
+            LunarPhase::LastQuarter => "🌗",
+            LunarPhase::WaningCrescent => "🌘",
+        }
+    }
+
+    pub fn latin_name(&self) -> &'static str {
+        match self {
+            LunarPhase::New => "Luna nova",
+            LunarPhase::WaxingCrescent => "Luna crescens",
+            LunarPhase::FirstQuarter => "Luna dimidia crescens",
+            LunarPhase::WaxingGibbous => "Luna gibbosa crescens",
+            LunarPhase::Full => "Luna plena",
+            LunarPhase::WaningGibbous => "Luna gibbosa decrescens",
+            LunarPhase::LastQuarter => "Luna dimidia decrescens",
+            LunarPhase::WaningCrescent => "Luna decrescens",
+        }
+    }
+}
+
+/// The Moon's phase at an instant.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MoonPhase {
+    /// Moon's longitude minus the Sun's, in degrees within [0, 360); 0 at new
+    /// moon, 180 at full
+    pub elongation: f64,
+    /// Illuminated fraction of the disc, in percent
+    pub illumination: f64,
+    /// Days since the last new moon
+    pub age: f64,
+    pub phase: LunarPhase,
+    /// Language `Display` names the phase in
+    #[serde(skip)]
+    pub language: Language,
+}
+
+impl MoonPhase {
+    /// Name of the phase in the chosen language.
+    pub fn phase_name(&self) -> &'static str {
+        self.language.phase(self.phase)
+    }
+}
+
+impl fmt::Display for MoonPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:.0}% {:.1}d", self.phase_name(), self.illumination, self.age)
+    }
+}
+
+/// The moment of a new or full moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Syzygy {
+    /// `LunarPhase::New` or `LunarPhase::Full`
+    pub phase: LunarPhase,
+    pub time: DateTime<Tz>,
+}
+
+impl ThelemicDate {
+    /// Computes the Moon's phase, illumination and age at Julian Day `jd`.
+    pub fn moon_phase(&self, jd: f64, language: Language) -> MoonPhase {
+        let jde = self.ephemeris_day(jd);
+        let (_sun_pos, sun_dist) = sun::geocent_ecl_pos(jde);
+        let (moon_pos, moon_dist) = lunar::geocent_ecl_pos(jde);
+        let elongation = self.elongation(jd);
+
+        // Phase angle at the Moon between the Sun and the Earth (Meeus, ch. 48)
+        let cos_psi = moon_pos.lat.cos() * elongation.to_radians().cos();
+        let psi = cos_psi.acos();
+        let sun_dist = sun_dist * AU_KM;
+        let phase_angle = f64::atan2(sun_dist * psi.sin(), moon_dist - sun_dist * cos_psi);
+        let illumination = (1.0 + phase_angle.cos()) / 2.0 * 100.0;
+
+        MoonPhase {
+            elongation,
+            illumination,
+            age: jd - self.previous_new_moon(jd),
+            phase: LunarPhase::from_elongation(elongation),
+            language,
+        }
+    }
+
+    /// The Moon's elongation east of the Sun at Julian Day `jd`, in degrees
+    /// within [0, 360).
+    fn elongation(&self, jd: f64) -> f64 {
+        (self.moon_longitude(jd) - self.sun_longitude(jd)).to_degrees().rem_euclid(360.0)
+    }
+
+    /// Finds every new and full moon between two instants, in time order.
+    pub fn syzygies(&self, from: &DateTime<Tz>, to: &DateTime<Tz>)
+        -> Result<Vec<Syzygy>, Box<dyn std::error::Error>> {
+        let start = Self::julian_day(&from.naive_utc());
+        let end = Self::julian_day(&to.naive_utc());
+        let mut found = Vec::new();
+        for (phase, elongation) in [(LunarPhase::New, 0.0), (LunarPhase::Full, 180.0)] {
+            let mut jd = start;
+            loop {
+                let at = self.next_elongation(jd, elongation);
+                if at >= end {
+                    break;
+                }
+                if at >= start {
+                    let time: DateTime<Utc> = Self::utc_from_julian_day(at).ok_or("Time out of range")?;
+                    found.push(Syzygy { phase, time: time.with_timezone(&from.timezone()) });
+                }
+                // Continue from well clear of this one
+                jd = at + SYNODIC_MONTH / 2.0;
+            }
+        }
+        found.sort_by_key(|syzygy| syzygy.time);
+        Ok(found)
+    }
+
+    /// Julian Day at which the Moon's elongation next reaches `elongation`
+    /// degrees after `jd`, or just before it when already within a fraction
+    /// of a second.
+    fn next_elongation(&self, jd: f64, elongation: f64) -> f64 {
+        // Step ahead by the remaining elongation at the mean rate, then home in
+        let mut at = jd + (elongation - self.elongation(jd)).rem_euclid(360.0) / 360.0 * SYNODIC_MONTH;
+        for _ in 0..20 {
+            let offset = (self.elongation(at) - elongation + 180.0).rem_euclid(360.0) - 180.0;
+            at -= offset / 360.0 * SYNODIC_MONTH;
+            if offset.abs() < 1e-6 {
+                break;
+            }
+        }
+        at
+    }
+
+    /// Julian Day of the last new moon at or before `jd`.
+    fn previous_new_moon(&self, jd: f64) -> f64 {
+        let elongation_at = |jd: f64| self.elongation(jd);
+
+        // Step back by the elongation at the mean rate, then home in on 0°
+        let mut new_moon = jd - elongation_at(jd) / 360.0 * SYNODIC_MONTH;
+        for _ in 0..20 {
+            let offset = (elongation_at(new_moon) + 180.0).rem_euclid(360.0) - 180.0;
+            new_moon -= offset / 360.0 * SYNODIC_MONTH;
+            if offset.abs() < 1e-6 {
+                break;
+            }
+        }
+        new_moon.min(jd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::UTC;
+
+    fn jd(y: i32, m: u32, d: u32, h: u32, min: u32) -> f64 {
+        ThelemicDate::julian_day(&UTC.with_ymd_and_hms(y, m, d, h, min, 0).unwrap().naive_utc())
+    }
+
+    #[test]
+    fn measures_the_moon_at_new_and_full() {
+        let date_data = ThelemicDate::new().with_cache(None);
+
+        // New moon of 2025 January 29, 12:36 UT
+        let new = date_data.moon_phase(jd(2025, 1, 29, 13, 36), Language::English);
+        assert_eq!(new.phase, LunarPhase::New);
+        assert!(new.illumination < 0.5, "{}", new.illumination);
+        assert!((new.age - 1.0 / 24.0).abs() < 0.01, "{}", new.age);
+
+        // Full moon of 2025 January 13, 22:27 UT, fourteen days after the
+        // new moon of 2024 December 30, 22:27 UT
+        let full = date_data.moon_phase(jd(2025, 1, 13, 22, 27), Language::English);
+        assert_eq!(full.phase, LunarPhase::Full);
+        assert!(full.illumination > 99.5, "{}", full.illumination);
+        assert!((full.elongation - 180.0).abs() < 0.1, "{}", full.elongation);
+        assert!((full.age - 14.0).abs() < 0.01, "{}", full.age);
+        assert_eq!(full.to_string(), "Full Moon 100% 14.0d");
+    }
+
+    #[test]
+    fn finds_new_and_full_moons() {
+        let date_data = ThelemicDate::new().with_cache(None);
+        let from = UTC.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let to = UTC.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        let syzygies = date_data.syzygies(&from, &to).unwrap();
+        let expected = [
+            (LunarPhase::Full, UTC.with_ymd_and_hms(2025, 1, 13, 22, 27, 0).unwrap()),
+            (LunarPhase::New, UTC.with_ymd_and_hms(2025, 1, 29, 12, 36, 0).unwrap()),
+            (LunarPhase::Full, UTC.with_ymd_and_hms(2025, 2, 12, 13, 53, 0).unwrap()),
+            (LunarPhase::New, UTC.with_ymd_and_hms(2025, 2, 28, 0, 45, 0).unwrap()),
+        ];
+        assert_eq!(syzygies.len(), expected.len());
+        for (syzygy, (phase, time)) in syzygies.iter().zip(expected) {
+            assert_eq!(syzygy.phase, phase);
+            assert!((syzygy.time - time).num_seconds().abs() < 60, "{:?} {}", phase, syzygy.time);
+        }
+    }
+}
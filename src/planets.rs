@@ -0,0 +1,103 @@
+use astro::planet::{self, Planet as AstroPlanet};
+use astro::{pluto, time};
+
+use serde::Serialize;
+
+use crate::Position;
+
+/// The planets reported alongside the Sun and Moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    Pluto,
+}
+
+/// A planet's place on the zodiac.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PlanetPosition {
+    pub planet: Planet,
+    pub position: Position,
+}
+
+impl Planet {
+    pub const ALL: [Planet; 8] = [
+        Planet::Mercury, Planet::Venus, Planet::Mars, Planet::Jupiter,
+        Planet::Saturn, Planet::Uranus, Planet::Neptune, Planet::Pluto
+    ];
+
+    /// General precession in longitude, in degrees per Julian century.
+    const PRECESSION: f64 = 1.396_971_3;
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Planet::Mercury => "Mercury",
+            Planet::Venus => "Venus",
+            Planet::Mars => "Mars",
+            Planet::Jupiter => "Jupiter",
+            Planet::Saturn => "Saturn",
+            Planet::Uranus => "Uranus",
+            Planet::Neptune => "Neptune",
+            Planet::Pluto => "Pluto",
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Planet::Mercury => "☿",
+            Planet::Venus => "♀",
+            Planet::Mars => "♂",
+            Planet::Jupiter => "♃",
+            Planet::Saturn => "♄",
+            Planet::Uranus => "♅",
+            Planet::Neptune => "♆",
+            Planet::Pluto => "♇",
+        }
+    }
+
+    /// Geocentric ecliptic longitude and latitude in radians, corrected for
+    /// light-time and referred to the mean equinox of the date. Pluto's theory
+    /// only covers 1885 to 2099, so `None` is returned for it outside those
+    /// years.
+    pub fn geocent_ecl_coords(&self, jd: f64) -> Option<(f64, f64)> {
+        let astro_planet = match self {
+            Planet::Mercury => AstroPlanet::Mercury,
+            Planet::Venus => AstroPlanet::Venus,
+            Planet::Mars => AstroPlanet::Mars,
+            Planet::Jupiter => AstroPlanet::Jupiter,
+            Planet::Saturn => AstroPlanet::Saturn,
+            Planet::Uranus => AstroPlanet::Uranus,
+            Planet::Neptune => AstroPlanet::Neptune,
+            Planet::Pluto => return Self::pluto_geocent_ecl_coords(jd),
+        };
+        let (ecl_point, _planet_earth_dist) = planet::geocent_apprnt_ecl_coords(&astro_planet, jd);
+        Some((ecl_point.long, ecl_point.lat))
+    }
+
+    fn pluto_geocent_ecl_coords(jd: f64) -> Option<(f64, f64)> {
+        let (year, _, _) = time::date_frm_julian_day(jd).ok()?;
+        if !(1885..=2099).contains(&year) {
+            return None;
+        }
+
+        let (l0, b0, r0) = planet::heliocent_coords(&AstroPlanet::Earth, jd);
+        // Pluto's position is referred to J2000.0; move it to the equinox of
+        // the date to match the other bodies
+        let heliocent = |jd: f64| {
+            let (l, b, r) = pluto::heliocent_pos(jd);
+            (l + (Self::PRECESSION * time::julian_cent(jd)).to_radians(), b, r)
+        };
+
+        // Correct for light-time, as `geocent_apprnt_ecl_coords` does for the others
+        let (l1, b1, r1) = heliocent(jd);
+        let (_, _, _, light_time) = planet::geocent_geomet_ecl_coords(l0, b0, r0, l1, b1, r1);
+        let (l2, b2, r2) = heliocent(jd - light_time);
+        let (long, lat, _dist, _light_time) = planet::geocent_geomet_ecl_coords(l0, b0, r0, l2, b2, r2);
+        Some((long, lat))
+    }
+}
@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use crate::{Style, Template};
+
+/// Layouts of the date line after the conventions Thelemic bodies date
+/// their documents by, each a template drawn in a style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// "☉ in 1º Leo, ☽ in 16º Cancer, dies Mercurii, Anno Vxi", with
+    /// "a.e.n." after years before the era
+    Traditional,
+    /// The traditional form with the era, "… Anno Vxi e.n." (or "a.e.n.")
+    TraditionalEn,
+    /// The traditional form with the era and the Gregorian date, "… Anno
+    /// Vxi e.n. (23 July 2025 e.v.)"
+    TraditionalEv,
+    /// The year first and glyphs throughout, "An Vxi ☉ 1° ♌ ☽ 16° ♋"
+    Heading,
+    /// The heading with the era, "An Vxi e.n. ☉ 1° ♌ ☽ 16° ♋"
+    HeadingEn,
+    /// The heading with the Gregorian date, "An Vxi ☉ 1° ♌ ☽ 16° ♋ (23
+    /// July 2025 e.v.)"
+    HeadingEv,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 6] = [
+        Preset::Traditional, Preset::TraditionalEn, Preset::TraditionalEv,
+        Preset::Heading, Preset::HeadingEn, Preset::HeadingEv,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Traditional => "traditional",
+            Preset::TraditionalEn => "traditional-en",
+            Preset::TraditionalEv => "traditional-ev",
+            Preset::Heading => "heading",
+            Preset::HeadingEn => "heading-en",
+            Preset::HeadingEv => "heading-ev",
+        }
+    }
+
+    /// The preset's layout in the template language.
+    pub fn template_source(&self) -> &'static str {
+        match self {
+            Preset::Traditional => {
+                "{sun.glyph} {sun.phrase}, {moon.glyph} {moon.phrase}, {weekday.full}, {anno.word} {anno}[ {anno.ante}]"
+            }
+            Preset::TraditionalEn => {
+                "{sun.glyph} {sun.phrase}, {moon.glyph} {moon.phrase}, {weekday.full}, {anno.word} {anno} {anno.abbr}"
+            }
+            Preset::TraditionalEv => {
+                "{sun.glyph} {sun.phrase}, {moon.glyph} {moon.phrase}, {weekday.full}, {anno.word} {anno} {anno.abbr} ({date.long} e.v.)"
+            }
+            Preset::Heading => {
+                "{anno.word_short} {anno}[ {anno.ante}] {sun.glyph} {sun.angle} {sun.sign_glyph} {moon.glyph} {moon.angle} {moon.sign_glyph}"
+            }
+            Preset::HeadingEn => {
+                "{anno.word_short} {anno} {anno.abbr} {sun.glyph} {sun.angle} {sun.sign_glyph} {moon.glyph} {moon.angle} {moon.sign_glyph}"
+            }
+            Preset::HeadingEv => {
+                "{anno.word_short} {anno}[ {anno.ante}] {sun.glyph} {sun.angle} {sun.sign_glyph} {moon.glyph} {moon.angle} {moon.sign_glyph} ({date.long} e.v.)"
+            }
+        }
+    }
+
+    pub fn template(&self) -> Template {
+        self.template_source().parse().expect("Preset templates are valid")
+    }
+
+    /// The style degrees are written in, unless another is asked for.
+    pub fn style(&self) -> Style {
+        match self {
+            Preset::Traditional | Preset::TraditionalEn | Preset::TraditionalEv => Style::Text,
+            Preset::Heading | Preset::HeadingEn | Preset::HeadingEv => Style::Glyph,
+        }
+    }
+}
+
+impl FromStr for Preset {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Preset::ALL.into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| format!(
+                "Unknown preset: {} (expected one of {})",
+                s,
+                Preset::ALL.iter().map(|preset| preset.name()).collect::<Vec<_>>().join(", ")
+            ).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Language, ThelemicDate};
+    use chrono::TimeZone;
+    use chrono_tz::Europe::London;
+
+    #[test]
+    fn reads_every_preset_by_name() {
+        for preset in Preset::ALL {
+            assert_eq!(preset.name().parse::<Preset>().unwrap(), preset);
+            preset.template();
+        }
+        assert_eq!("Traditional_EV".parse::<Preset>().unwrap(), Preset::TraditionalEv);
+        assert!("modern".parse::<Preset>().is_err());
+    }
+
+    #[test]
+    fn writes_the_year_in_the_date_language() {
+        let dt = London.with_ymd_and_hms(2025, 7, 23, 12, 0, 0).unwrap();
+        let mut value = ThelemicDate::new().with_cache(None).at(&dt).unwrap();
+        let render = |value: &crate::ThelemicDateValue, preset: Preset| preset.template().render(value);
+        assert_eq!(render(&value, Preset::Traditional), "☉ in 0º Leo, ☽ in 13º Cancer, dies Mercurii, Anno Vxi");
+        assert!(render(&value, Preset::Heading).starts_with("An Vxi ☉"));
+
+        value.language = Language::French;
+        for position in [&mut value.sun, &mut value.moon] {
+            position.language = Language::French;
+        }
+        assert_eq!(
+            render(&value, Preset::TraditionalEv),
+            "☉ à 0º du Lion, ☽ à 13º du Cancer, jour de Mercure, An Vxi e.n. (23 juillet 2025 e.v.)"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use astro::{coords, ecliptic, nutation, sun, time};
+use std::f64::consts::PI;
+
+use crate::{Location, ThelemicDate};
+
+/// Altitude of the Sun's centre at rising and setting, in degrees, allowing
+/// for refraction and the Sun's semi-diameter.
+const SUNRISE_ALTITUDE: f64 = -0.8333;
+
+/// The four adorations of Liber Resh vel Helios.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Adoration {
+    /// At sunrise
+    Ra,
+    /// At noon
+    Ahathoor,
+    /// At sunset
+    Tum,
+    /// At midnight
+    Khephra,
+}
+
+impl Adoration {
+    pub const ALL: [Adoration; 4] = [Adoration::Ra, Adoration::Ahathoor, Adoration::Tum, Adoration::Khephra];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Adoration::Ra => "Ra",
+            Adoration::Ahathoor => "Ahathoor",
+            Adoration::Tum => "Tum",
+            Adoration::Khephra => "Khephra",
+        }
+    }
+
+    /// The solar event the adoration is performed at.
+    pub fn event(&self) -> &'static str {
+        match self {
+            Adoration::Ra => "sunrise",
+            Adoration::Ahathoor => "solar noon",
+            Adoration::Tum => "sunset",
+            Adoration::Khephra => "solar midnight",
+        }
+    }
+}
+
+/// The adorations of one day: sunrise, solar noon, sunset, and the solar
+/// midnight that follows. Sunrise and sunset are `None` where the Sun does
+/// not rise or set that day, as within the polar circles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReshDay {
+    pub date: NaiveDate,
+    pub sunrise: Option<DateTime<Tz>>,
+    pub noon: DateTime<Tz>,
+    pub sunset: Option<DateTime<Tz>>,
+    pub midnight: DateTime<Tz>,
+}
+
+impl ReshDay {
+    /// Each adoration with its time, in the order they fall.
+    pub fn adorations(&self) -> [(Adoration, Option<DateTime<Tz>>); 4] {
+        [
+            (Adoration::Ra, self.sunrise),
+            (Adoration::Ahathoor, Some(self.noon)),
+            (Adoration::Tum, self.sunset),
+            (Adoration::Khephra, Some(self.midnight)),
+        ]
+    }
+}
+
+impl ThelemicDate {
+    /// Computes the adoration times of a local calendar day at a location,
+    /// which must have coordinates.
+    pub fn resh(&self, date: NaiveDate, location: &Location) -> Result<ReshDay, Box<dyn std::error::Error>> {
+        let (latitude, longitude, tz) = self.resh_coordinates(location)?;
+        Self::resh_at(date, latitude, longitude, &tz)
+    }
+
+    /// Finds the first adoration after `now` at a location, with its time.
+    pub fn next_adoration(&self, now: &DateTime<Tz>, location: &Location)
+        -> Result<(Adoration, DateTime<Tz>), Box<dyn std::error::Error>> {
+        let (latitude, longitude, tz) = self.resh_coordinates(location)?;
+        let now = now.with_timezone(&tz);
+        // Yesterday's midnight may still be ahead, and near the poles the
+        // next event may be a day or more away
+        let today = now.date_naive();
+        let mut upcoming = Vec::new();
+        for offset in -1..=2 {
+            let day = Self::resh_at(today + Duration::days(offset), latitude, longitude, &tz)?;
+            upcoming.extend(day.adorations().into_iter()
+                .filter_map(|(adoration, time)| Some((adoration, time?)))
+                .filter(|(_, time)| *time > now));
+        }
+        upcoming.into_iter()
+            .min_by_key(|(_, time)| *time)
+            .ok_or_else(|| "No adoration found in the coming days".into())
+    }
+
+    fn resh_coordinates(&self, location: &Location) -> Result<(f64, f64, Tz), Box<dyn std::error::Error>> {
+        let resolved = self.resolve(location)?;
+        let (latitude, longitude) = resolved.coordinates()
+            .ok_or("Adoration times need coordinates; give a place or --coords rather than a timezone")?;
+        Ok((latitude, longitude, resolved.timezone))
+    }
+
+    /// Computes the adoration times of a local calendar day at a latitude and
+    /// longitude in degrees, east positive.
+    pub fn resh_at(date: NaiveDate, latitude: f64, longitude: f64, tz: &Tz)
+        -> Result<ReshDay, Box<dyn std::error::Error>> {
+        let lat = latitude.to_radians();
+        let lon = longitude.to_radians();
+
+        // Start from the local clock's noon, which is within a few hours of the Sun's
+        let clock_noon = tz.from_local_datetime(&date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap()))
+            .earliest()
+            .ok_or("Invalid local time")?;
+        let noon = solve_hour_angle(Self::julian_day(&clock_noon.naive_utc()), lon, |_| Some(0.0))
+            .ok_or("Could not find solar noon")?;
+        let midnight = solve_hour_angle(noon + 0.5, lon, |_| Some(PI))
+            .ok_or("Could not find solar midnight")?;
+
+        // Hour angle of the Sun at rising, or `None` if it stays up or down
+        let rising_hour_angle = |decl: f64| {
+            let cos_h0 = (SUNRISE_ALTITUDE.to_radians().sin() - lat.sin() * decl.sin())
+                / (lat.cos() * decl.cos());
+            (cos_h0.abs() <= 1.0).then(|| cos_h0.acos())
+        };
+        let sunrise = solve_hour_angle(noon - 0.25, lon, |decl| Some(-rising_hour_angle(decl)?));
+        let sunset = solve_hour_angle(noon + 0.25, lon, rising_hour_angle);
+
+        let to_local = |jd: f64| -> Result<DateTime<Tz>, Box<dyn std::error::Error>> {
+            let utc: DateTime<Utc> = Self::utc_from_julian_day(jd).ok_or("Time out of range")?;
+            Ok(utc.with_timezone(tz))
+        };
+        Ok(ReshDay {
+            date,
+            sunrise: sunrise.map(to_local).transpose()?,
+            noon: to_local(noon)?,
+            sunset: sunset.map(to_local).transpose()?,
+            midnight: to_local(midnight)?,
+        })
+    }
+}
+
+/// The Sun's local hour angle in (-π, π] and declination at Julian Day `jd`
+/// for an east longitude, all in radians.
+fn sun_hour_angle(jd: f64, lon: f64) -> (f64, f64) {
+    // The solar theory runs on Terrestrial Time, sidereal time on Universal Time
+    let (sun_pos, sun_dist) = sun::geocent_ecl_pos(jd + ThelemicDate::delta_t(jd));
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(jd);
+    let oblq = ecliptic::mn_oblq_IAU(jd) + nut_in_oblq;
+    let aberration = (-20.4898 / 3600.0_f64).to_radians() / sun_dist;
+    let long = sun_pos.long + nut_in_long + aberration;
+
+    let ra = coords::asc_frm_ecl(long, sun_pos.lat, oblq);
+    let decl = coords::dec_frm_ecl(long, sun_pos.lat, oblq);
+    let sidereal = time::apprnt_sidr(time::mn_sidr(jd), nut_in_long, oblq);
+    (wrap(sidereal + lon - ra), decl)
+}
+
+/// Iterates from `jd` to the moment the Sun reaches the hour angle returned by
+/// `target` for its current declination, or `None` if `target` has no answer.
+fn solve_hour_angle(mut jd: f64, lon: f64, target: impl Fn(f64) -> Option<f64>) -> Option<f64> {
+    for _ in 0..20 {
+        let (hour_angle, decl) = sun_hour_angle(jd, lon);
+        let offset = wrap(target(decl)? - hour_angle);
+        // The Sun's hour angle turns once a day
+        jd += offset / (2.0 * PI);
+        if offset.abs() < 1e-7 {
+            break;
+        }
+    }
+    Some(jd)
+}
+
+/// Wraps an angle in radians into (-π, π].
+fn wrap(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI { wrapped - 2.0 * PI } else { wrapped }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::{London, Oslo};
+
+    #[test]
+    fn finds_the_adorations_in_london() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 23).unwrap();
+        let day = ThelemicDate::resh_at(date, 51.5074, -0.1278, &London).unwrap();
+        let at = |d: u32, h: u32, m: u32| London.with_ymd_and_hms(2025, 7, d, h, m, 0).unwrap();
+        // Published times for the day, to the minute
+        let expected = [
+            (Adoration::Ra, at(23, 5, 12)),
+            (Adoration::Ahathoor, at(23, 13, 7)),
+            (Adoration::Tum, at(23, 21, 1)),
+            (Adoration::Khephra, at(24, 1, 7)),
+        ];
+        for ((adoration, time), (expected_adoration, expected_time)) in day.adorations().into_iter().zip(expected) {
+            assert_eq!(adoration, expected_adoration);
+            let time = time.unwrap();
+            assert!((time - expected_time).num_seconds().abs() <= 90, "{}: {}", adoration.name(), time);
+        }
+    }
+
+    #[test]
+    fn leaves_out_sunrise_and_sunset_within_the_arctic_circle() {
+        // Tromsø, under the midnight sun in June and the polar night in December
+        for (month, day) in [(6, 21), (12, 21)] {
+            let date = NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+            let day = ThelemicDate::resh_at(date, 69.6492, 18.9553, &Oslo).unwrap();
+            assert_eq!((day.sunrise, day.sunset), (None, None), "{}", date);
+            assert_eq!(day.noon.date_naive(), date);
+            assert!((day.midnight - day.noon - Duration::hours(12)).num_minutes().abs() <= 1);
+        }
+    }
+}
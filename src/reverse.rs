@@ -0,0 +1,484 @@
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+use crate::{hours, to_ascii, Anno, Language, Location, Planet, Position, ThelemicDate};
+
+/// A sign and degree to look for, as read from a Thelemic date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignDegree {
+    /// Index into `ThelemicDate::SIGNS`
+    pub sign: usize,
+    pub degree: i32,
+}
+
+impl SignDegree {
+    fn matches(&self, position: &Position) -> bool {
+        ThelemicDate::SIGNS[self.sign].0 == position.sign && self.degree == position.degree
+    }
+}
+
+/// The components of a Thelemic date string. Only the Anno is required; any
+/// other component left out matches every instant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThelemicDateQuery {
+    pub sun: Option<SignDegree>,
+    pub moon: Option<SignDegree>,
+    pub planets: Vec<(Planet, SignDegree)>,
+    /// Index into `ThelemicDate::DAYS_OF_WEEK`
+    pub weekday: Option<usize>,
+    pub anno: Anno,
+}
+
+/// A span of time during which every component of a query holds. `end` is
+/// the first instant at which it no longer does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateWindow {
+    pub start: DateTime<Tz>,
+    pub end: DateTime<Tz>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Body {
+    Sun,
+    Moon,
+    Planet(Planet),
+}
+
+impl FromStr for ThelemicDateQuery {
+    type Err = Box<dyn std::error::Error>;
+
+    /// Parses the line printed by `tdate`, e.g.
+    /// "☉ in 1º Leo : ☽ in 16º Cancer : dies Mercurii : Anno Vxi æræ legis",
+    /// in any of its languages and styles.
+    ///
+    /// Separators, prepositions, articles, "dies", the degree sign, minutes
+    /// of arc, the planetary hour and the era wording are all optional,
+    /// bodies may be written "Sun"/"Sol" and "Moon"/"Luna", signs and days in
+    /// any language or as glyphs, and "An" may stand for "Anno". Planets, as
+    /// printed with `--planets`, may be given by glyph or name. Anything in
+    /// parentheses, such as the Gregorian date the presets add, is skipped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut depth = 0usize;
+        let spaced: String = s.chars()
+            .flat_map(|c| match c {
+                '(' => { depth += 1; vec![' '] }
+                ')' => { depth = depth.saturating_sub(1); vec![' '] }
+                _ if depth > 0 => vec![],
+                ':' | ',' | ';' | '|' | 'º' | '°' => vec![' '],
+                '☉' | '☽' | '☿' | '♀' | '♂' | '♃' | '♄' | '♅' | '♆' | '♇' => vec![' ', c, ' '],
+                _ => vec![c],
+            })
+            .collect();
+        let tokens: Vec<&str> = spaced.split_whitespace().collect();
+
+        let mut sun = None;
+        let mut moon = None;
+        let mut planets = Vec::new();
+        let mut weekday = None;
+        let mut anno = None;
+        let mut before_era = false;
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i];
+            let word = fold(token);
+            i += 1;
+
+            let body = match word.as_str() {
+                _ if token == "☉" || word == "sun" || word == "sol" => Some(Body::Sun),
+                _ if token == "☽" || word == "moon" || word == "luna" => Some(Body::Moon),
+                _ => Planet::ALL.iter()
+                    .find(|planet| planet.glyph() == token || planet.name().eq_ignore_ascii_case(token))
+                    .map(|planet| Body::Planet(*planet)),
+            };
+            if let Some(body) = body {
+                let mut j = i;
+                if tokens.get(j).is_some_and(|t| PREPOSITIONS.contains(&fold(t).as_str())) {
+                    j += 1;
+                }
+                let Some(degree) = tokens.get(j).and_then(|t| parse_degree(t)) else {
+                    // A body without a degree is a planetary day, as the glyph
+                    // style writes it ("☿"), or a Spanish or Portuguese day
+                    // ("día del Sol")
+                    let day = hours::RULER_GLYPHS.iter().position(|glyph| *glyph == token)
+                        .or_else(|| Self::weekday_index(token))
+                        .ok_or_else(|| format!("Expected a degree after {}", token))?;
+                    weekday = Some(day);
+                    continue;
+                };
+                j += 1;
+                // Minutes and seconds of arc, and the articles before a sign
+                while tokens.get(j).is_some_and(|t| t.ends_with(['\'', '"']) || ARTICLES.contains(&fold(t).as_str())) {
+                    j += 1;
+                }
+                let sign = tokens.get(j)
+                    .and_then(|t| Self::sign_index(t))
+                    .ok_or_else(|| format!("Expected a sign after {}º", degree))?;
+                i = j + 1;
+
+                let position = SignDegree { sign, degree };
+                match body {
+                    Body::Sun => sun = Some(position),
+                    Body::Moon => moon = Some(position),
+                    Body::Planet(planet) => {
+                        planets.retain(|(other, _)| *other != planet);
+                        planets.push((planet, position));
+                    }
+                }
+                continue;
+            }
+
+            match word.as_str() {
+                _ if IGNORED.contains(&word.as_str()) => {}
+                "ante" | "antes" | "vor" | "avant" | "a.e.n." | "a.e.l." => before_era = true,
+                "anno" | "an" | "an." | "ano" | "jahr" => {
+                    let numeral = tokens.get(i).ok_or("Expected a numeral after Anno")?;
+                    anno = Some(numeral.parse::<Anno>()?);
+                    i += 1;
+                }
+                // The planetary hour is not part of the date; skip its ruler
+                "hora" => {
+                    if tokens.get(i).is_some_and(|t| hours::RULER_GLYPHS.contains(t)) {
+                        i += 1;
+                    }
+                    if tokens.get(i).is_some_and(|t| ThelemicDate::DAYS_OF_WEEK.iter().any(|day| day.eq_ignore_ascii_case(t))) {
+                        i += 1;
+                    }
+                }
+                _ => {
+                    weekday = Some(
+                        Self::weekday_index(token)
+                            .ok_or_else(|| format!("Unrecognised component: {}", token))?
+                    );
+                }
+            }
+        }
+
+        let anno = anno.ok_or("The date has no Anno")?;
+        let anno = if before_era { Anno::new(-anno.years())? } else { anno };
+
+        Ok(ThelemicDateQuery { sun, moon, planets, weekday, anno })
+    }
+}
+
+/// Words before a degree, in every language.
+const PREPOSITIONS: [&str; 5] = ["in", "en", "em", "auf", "a"];
+
+/// Articles between a degree and its sign, or a day and its ruler.
+const ARTICLES: [&str; 8] = ["de", "la", "da", "do", "del", "des", "der", "du"];
+
+/// Words of the weekday and era wording that carry no information, folded
+/// to ASCII.
+const IGNORED: [&str; 28] = [
+    "in", "en", "em", "auf", "a", "de", "la", "da", "do", "del", "des", "der", "du",
+    "dies", "dia", "tag", "jour",
+    "aerae", "aeram", "legis", "era", "aera", "l'ere", "ley", "lei", "gesetzes", "loi", "e.n.",
+];
+
+/// Lower-cases a word and spells it in ASCII, so that "Löwe" matches "loewe"
+/// and "día" matches "dia".
+fn fold(word: &str) -> String {
+    to_ascii(&word.to_lowercase())
+}
+
+/// Reads a degree as written under any `--degrees` or `--style`, e.g. "1",
+/// "1.39" or "1d23'", keeping its whole degrees.
+fn parse_degree(token: &str) -> Option<i32> {
+    let digits = token.find(|c: char| !c.is_ascii_digit()).unwrap_or(token.len());
+    let rest = &token[digits..];
+    if !(rest.is_empty() || rest.starts_with('.') || rest.starts_with('d')) {
+        return None;
+    }
+    token[..digits].parse().ok()
+        // 30 is the last degree when degrees are counted from 1
+        .filter(|d| (0..=30).contains(d))
+}
+
+impl ThelemicDateQuery {
+    /// Index of a sign given by glyph or by name in any language, including
+    /// the forms taken after a preposition such as the Latin "Leone".
+    fn sign_index(token: &str) -> Option<usize> {
+        let word = fold(token);
+        ThelemicDate::SIGNS.iter().position(|(_, glyph)| *glyph == token)
+            .or_else(|| (0..12).find(|&sign| {
+                Language::ALL.iter().any(|language| {
+                    fold(language.sign(sign)) == word
+                        || language.position("", sign).split_whitespace().last().map(fold) == Some(word.clone())
+                })
+            }))
+    }
+
+    /// Index of a planetary day given by its ruler in any language, e.g.
+    /// "Mercurii", "Merkur" or "Mondes".
+    fn weekday_index(token: &str) -> Option<usize> {
+        let word = fold(token);
+        (0..7).find(|&day| {
+            Language::ALL.iter().any(|language| {
+                fold(language.day_name(day)) == word
+                    || language.weekday(day).split_whitespace().last().map(fold) == Some(word.clone())
+            })
+        })
+    }
+}
+
+/// Half-open span of Unix timestamps, in seconds.
+type Span = (i64, i64);
+
+impl ThelemicDate {
+    /// Step used to scan for the Sun's degrees, which each last about a day.
+    const SUN_STEP: i64 = 3600;
+
+    /// Step used to scan for the Moon's degrees, which each last about two hours.
+    const MOON_STEP: i64 = 600;
+
+    /// Finds the Gregorian time windows in which a Thelemic date holds for a
+    /// location.
+    pub fn find(&self, query: &ThelemicDateQuery, location: &Location)
+        -> Result<Vec<DateWindow>, Box<dyn std::error::Error>> {
+        let tz = self.resolve(location)?.timezone;
+        self.find_in(query, &tz)
+    }
+
+    /// Finds the Gregorian time windows in which a Thelemic date holds, with
+    /// weekdays and the Anno boundary taken in timezone `tz`.
+    pub fn find_in(&self, query: &ThelemicDateQuery, tz: &Tz)
+        -> Result<Vec<DateWindow>, Box<dyn std::error::Error>> {
+        let year = query.anno.gregorian_year();
+        let start = Self::vernal_equinox(year, tz)?.timestamp();
+        let end = Self::vernal_equinox(year + 1, tz)?.timestamp();
+        let mut spans = vec![(start, end)];
+
+        if let Some(weekday) = query.weekday {
+            spans = intersect(&spans, &Self::weekday_spans(weekday, start, end, tz)?);
+        }
+        if let Some(target) = query.sun {
+            let found = scan(start, end, Self::SUN_STEP, |t| {
+                let jd = Self::julian_day_at(t);
+                target.matches(&self.position_at(self.sun_longitude(jd), jd))
+            });
+            spans = intersect(&spans, &found);
+        }
+        // Planets move no faster than the Sun's degree a day, bar Mercury at
+        // about two, so the Sun's step is fine enough for them too
+        for (planet, target) in &query.planets {
+            let mut found = Vec::new();
+            for &(from, to) in &spans {
+                found.extend(scan(from, to, Self::SUN_STEP, |t| {
+                    let jd = Self::julian_day_at(t);
+                    self.planet_longitude(*planet, jd)
+                        .is_some_and(|long| target.matches(&self.position_at(long, jd)))
+                }));
+            }
+            spans = found;
+        }
+        if let Some(target) = query.moon {
+            let mut found = Vec::new();
+            for &(from, to) in &spans {
+                found.extend(scan(from, to, Self::MOON_STEP, |t| {
+                    let jd = Self::julian_day_at(t);
+                    target.matches(&self.position_at(self.moon_longitude(jd), jd))
+                }));
+            }
+            spans = found;
+        }
+
+        spans.into_iter()
+            .map(|(from, to)| {
+                let start = Utc.timestamp_opt(from, 0).single().ok_or("Time out of range")?;
+                let end = Utc.timestamp_opt(to, 0).single().ok_or("Time out of range")?;
+                Ok(DateWindow { start: start.with_timezone(tz), end: end.with_timezone(tz) })
+            })
+            .collect()
+    }
+
+    fn julian_day_at(timestamp: i64) -> f64 {
+        timestamp as f64 / 86400.0 + Self::UNIX_EPOCH_JD
+    }
+
+    /// Local days falling on `weekday` between two timestamps.
+    fn weekday_spans(weekday: usize, start: i64, end: i64, tz: &Tz)
+        -> Result<Vec<Span>, Box<dyn std::error::Error>> {
+        let local_midnight = |date: chrono::NaiveDate| -> Result<i64, Box<dyn std::error::Error>> {
+            let midnight = date.and_time(NaiveTime::MIN);
+            // Where midnight is skipped by a DST change the day starts an hour later
+            let dt = tz.from_local_datetime(&midnight).earliest()
+                .or_else(|| tz.from_local_datetime(&(midnight + Duration::hours(1))).earliest())
+                .ok_or("Invalid local time")?;
+            Ok(dt.timestamp())
+        };
+
+        let first = Utc.timestamp_opt(start, 0).single().ok_or("Time out of range")?
+            .with_timezone(tz)
+            .date_naive();
+        let mut spans = Vec::new();
+        for date in first.iter_days() {
+            let from = local_midnight(date)?;
+            if from >= end {
+                break;
+            }
+            if Self::weekday_to_index(date.weekday()) == weekday {
+                let to = local_midnight(date.succ_opt().ok_or("Date out of range")?)?;
+                spans.push((from.max(start), to.min(end)));
+            }
+        }
+        Ok(spans)
+    }
+}
+
+/// Samples `matches` every `step` seconds over `[start, end)` and returns the
+/// spans where it holds, with each edge refined to the second. Spans shorter
+/// than `step` may be missed, so the step must be shorter than any span sought.
+fn scan(start: i64, end: i64, step: i64, matches: impl Fn(i64) -> bool) -> Vec<Span> {
+    // Narrows a change of state between `lo` and `hi` down to the first
+    // second at which `matches(t)` equals `state_at_hi`
+    let refine = |mut lo: i64, mut hi: i64, state_at_hi: bool| {
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if matches(mid) == state_at_hi {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        hi
+    };
+
+    let mut spans = Vec::new();
+    if start >= end {
+        return spans;
+    }
+    let mut open = matches(start).then_some(start);
+    let mut prev = start;
+    loop {
+        // The last sample is the last second inside the range
+        let t = (prev + step).min(end - 1);
+        if t <= prev {
+            break;
+        }
+        match (open, matches(t)) {
+            (None, true) => open = Some(refine(prev, t, true)),
+            (Some(from), false) => {
+                spans.push((from, refine(prev, t, false)));
+                open = None;
+            }
+            _ => {}
+        }
+        prev = t;
+    }
+    if let Some(from) = open {
+        spans.push((from, end));
+    }
+    spans
+}
+
+/// Intersects two sorted lists of disjoint spans.
+fn intersect(a: &[Span], b: &[Span]) -> Vec<Span> {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let from = a[i].0.max(b[j].0);
+        let to = a[i].1.min(b[j].1);
+        if from < to {
+            out.push((from, to));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Style;
+    use chrono_tz::Europe::London;
+
+    #[test]
+    fn scans_for_spans_to_the_second() {
+        // True for the odd hundreds of seconds
+        let odd = |t: i64| (t / 100) % 2 == 1;
+        assert_eq!(scan(0, 1000, 30, odd), vec![(100, 200), (300, 400), (500, 600), (700, 800), (900, 1000)]);
+        assert_eq!(scan(150, 450, 30, odd), vec![(150, 200), (300, 400)]);
+        assert_eq!(scan(0, 1000, 30, |_| false), vec![]);
+        assert_eq!(scan(0, 1000, 30, |_| true), vec![(0, 1000)]);
+        assert_eq!(scan(5, 5, 30, |_| true), vec![]);
+        assert_eq!(scan(9, 5, 30, |_| true), vec![]);
+    }
+
+    #[test]
+    fn intersects_spans() {
+        let a = [(0, 10), (20, 30), (40, 50)];
+        let b = [(5, 25), (28, 45)];
+        assert_eq!(intersect(&a, &b), vec![(5, 10), (20, 25), (28, 30), (40, 45)]);
+        assert_eq!(intersect(&a, &[(10, 20)]), vec![]);
+        assert_eq!(intersect(&a, &[]), vec![]);
+        assert_eq!(intersect(&[(0, 100)], &a), a.to_vec());
+    }
+
+    #[test]
+    fn finds_local_weekdays_across_a_clock_change() {
+        // Sunday 30 March 2025, when London's clocks go forward
+        let start = London.with_ymd_and_hms(2025, 3, 27, 12, 0, 0).unwrap().timestamp();
+        let end = London.with_ymd_and_hms(2025, 4, 6, 12, 0, 0).unwrap().timestamp();
+        let sundays = ThelemicDate::weekday_spans(6, start, end, &London).unwrap();
+        let midnight = |d| London.with_ymd_and_hms(2025, 3, d, 0, 0, 0).unwrap().timestamp();
+        assert_eq!(sundays, vec![
+            (midnight(30), midnight(31)),
+            (London.with_ymd_and_hms(2025, 4, 6, 0, 0, 0).unwrap().timestamp(), end),
+        ]);
+        assert_eq!(sundays[0].1 - sundays[0].0, 23 * 3600);
+    }
+
+    #[test]
+    fn parses_loose_forms() {
+        let query: ThelemicDateQuery = "Sun 1 Leo, Moon 16° Cancer, An Vxi".parse().unwrap();
+        assert_eq!(query.sun, Some(SignDegree { sign: 4, degree: 1 }));
+        assert_eq!(query.moon, Some(SignDegree { sign: 3, degree: 16 }));
+        assert_eq!(query.weekday, None);
+        assert_eq!(query.anno.years(), 121);
+
+        let query: ThelemicDateQuery = "Anno 0i a.e.n.".parse().unwrap();
+        assert_eq!(query.anno.years(), -1);
+        assert_eq!(query.sun, None);
+
+        assert!("☉ in 1º Leo".parse::<ThelemicDateQuery>().is_err());
+        assert!("☉ in 31º Leo : Anno Vxi".parse::<ThelemicDateQuery>().is_err());
+        assert!("☉ in 1º Lion : Anno Vxi : foo".parse::<ThelemicDateQuery>().is_err());
+    }
+
+    #[test]
+    fn parses_its_own_output_in_every_language_and_style() {
+        let dt = London.with_ymd_and_hms(2025, 7, 23, 12, 0, 0).unwrap();
+        let mut value = ThelemicDate::new().with_cache(None).at(&dt).unwrap();
+        for language in Language::ALL {
+            for style in [Style::Text, Style::Glyph, Style::Ascii] {
+                (value.language, value.style) = (language, style);
+                for position in [&mut value.sun, &mut value.moon] {
+                    (position.language, position.style) = (language, style);
+                }
+                let line = value.to_string();
+                let query: ThelemicDateQuery = line.parse().unwrap_or_else(|e| panic!("{}: {}", line, e));
+                assert_eq!(query.sun, Some(SignDegree { sign: 4, degree: 0 }), "{}", line);
+                assert_eq!(query.moon, Some(SignDegree { sign: 3, degree: 13 }), "{}", line);
+                assert_eq!(query.weekday, Some(2), "{}", line);
+                assert_eq!(query.anno, value.anno, "{}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_window_of_a_date() {
+        let dt = London.with_ymd_and_hms(2025, 7, 23, 12, 0, 0).unwrap();
+        let date_data = ThelemicDate::new().with_cache(None);
+        let query: ThelemicDateQuery = date_data.at(&dt).unwrap().to_string().parse().unwrap();
+        let windows = date_data.find_in(&query, &London).unwrap();
+        assert_eq!(windows.len(), 1);
+        assert!(windows[0].start <= dt && dt < windows[0].end);
+        // The Moon holds a degree for about two hours
+        assert!(windows[0].end - windows[0].start < Duration::hours(3));
+    }
+}
@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+/// How the date line is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Style {
+    /// Glyphs for the bodies and words for the rest, e.g. "☉ in 1º Leo : ☽ in
+    /// 16º Cancer : dies Mercurii : Anno Vxi æræ legis"
+    #[default]
+    Text,
+    /// Glyphs for every body, sign and day, e.g. "☉ 1° ♌ : ☽ 16° ♋ : ☿ : An Vxi"
+    Glyph,
+    /// Nothing outside ASCII, e.g. "Sun 1 Leo : Moon 16 Cancer : dies
+    /// Mercurii : Anno Vxi aerae legis"
+    Ascii,
+}
+
+impl FromStr for Style {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" => Ok(Style::Text),
+            "glyph" | "glyphs" => Ok(Style::Glyph),
+            "ascii" => Ok(Style::Ascii),
+            _ => Err(format!("Unknown style: {} (expected text, glyph or ascii)", s).into()),
+        }
+    }
+}
+
+/// Spells text in ASCII: ligatures and umlauts are written out ("æ" as
+/// "ae", "ö" as "oe"), other accents dropped, degree signs removed and
+/// anything else replaced by "?".
+pub fn to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            c if c.is_ascii() => out.push(c),
+            'æ' | 'ä' => out.push_str("ae"),
+            'Æ' | 'Ä' => out.push_str("Ae"),
+            'ö' => out.push_str("oe"),
+            'Ö' => out.push_str("Oe"),
+            'ü' => out.push_str("ue"),
+            'Ü' => out.push_str("Ue"),
+            'ß' => out.push_str("ss"),
+            'á' | 'à' | 'â' | 'ã' => out.push('a'),
+            'Á' | 'À' | 'Â' | 'Ã' => out.push('A'),
+            'é' | 'è' | 'ê' | 'ë' => out.push('e'),
+            'É' | 'È' | 'Ê' | 'Ë' => out.push('E'),
+            'í' | 'ì' | 'î' | 'ï' => out.push('i'),
+            'Í' | 'Ì' | 'Î' | 'Ï' => out.push('I'),
+            'ó' | 'ò' | 'ô' | 'õ' => out.push('o'),
+            'Ó' | 'Ò' | 'Ô' | 'Õ' => out.push('O'),
+            'ú' | 'ù' | 'û' => out.push('u'),
+            'Ú' | 'Ù' | 'Û' => out.push('U'),
+            'ç' => out.push('c'),
+            'Ç' => out.push('C'),
+            'ñ' => out.push('n'),
+            'Ñ' => out.push('N'),
+            'º' | '°' => {}
+            _ => out.push('?'),
+        }
+    }
+    out
+}
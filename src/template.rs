@@ -0,0 +1,325 @@
+use chrono::format::{Item, StrftimeItems};
+use std::str::FromStr;
+
+use crate::{to_ascii, Planet, Position, Style, ThelemicDate, ThelemicDateValue, Zodiac};
+
+/// A user-defined layout for a computed date, e.g.
+/// `"{sun.glyph} {sun.deg}° {sun.sign} | dies {weekday} | An {anno}"`.
+///
+/// Placeholders are written in braces and segments in square brackets are
+/// only printed when every placeholder inside them has a value, so
+/// `"[ : {phase}]"` disappears unless the Moon's phase was computed. Outside
+/// a segment, a placeholder without a value is left blank. `{{`,
+/// `}}`, `[[` and `]]` stand for literal braces and brackets.
+///
+/// Bodies (`sun`, `moon`, `mercury` ... `pluto`, `asc`, `mc`) stand for their
+/// sign and offer `.glyph`, `.sign`, `.sign_glyph`, `.deg`, `.angle` (the
+/// degree as written in the style, e.g. "1º23'" or "1°23'"), `.phrase` (where
+/// the body stands, e.g. "in 1º Leo" or "auf 1º Löwe") and `.long`;
+/// `weekday`, `weekday.full` and `weekday.index`, `anno`, `anno.word` (e.g. "Anno" or "Jahr"),
+/// `anno.word_short` (e.g. "An"), `anno.era`, `anno.abbr` ("e.n." or "a.e.n."),
+/// `anno.ante` ("a.e.n.", only before the era), `anno.years`, `anno.cycle_i` and `anno.cycle_ii`,
+/// `phase`, `phase.illumination`, `phase.age`, `hour`, `hour.glyph`,
+/// `houses`, `lst`, `jd`, `zodiac`, `ayanamsa`, `lat` and `lon` cover the other components, and
+/// `date`, `date.long` (e.g. "23 July 2025", in the date's language), `time`, `year`, `month`, `day`, `hour24`, `minute`, `tz` and
+/// `greg:FORMAT` (any strftime format) the Gregorian date.
+///
+/// Sign names, weekdays and the era follow the date's language, and in the
+/// ASCII style every placeholder is written in ASCII, glyphs giving way to
+/// names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(String),
+    Optional(Vec<Part>),
+}
+
+impl FromStr for Template {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        // Parts of each open bracket, innermost last
+        let mut stack: Vec<Vec<Part>> = vec![Vec::new()];
+        let push_char = |stack: &mut Vec<Vec<Part>>, c: char| {
+            let parts = stack.last_mut().expect("The outermost level is never closed");
+            match parts.last_mut() {
+                Some(Part::Literal(text)) => text.push(c),
+                _ => parts.push(Part::Literal(c.to_string())),
+            }
+        };
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' | '}' | '[' | ']' if chars.peek() == Some(&c) => {
+                    chars.next();
+                    push_char(&mut stack, c);
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("Unclosed placeholder in template: {{{}", name).into()),
+                        }
+                    }
+                    let name = name.trim().to_string();
+                    if !is_known(&name) {
+                        return Err(format!("Unknown template placeholder: {{{}}}", name).into());
+                    }
+                    stack.last_mut().expect("The outermost level is never closed").push(Part::Field(name));
+                }
+                '}' => return Err("Unmatched '}' in template (write '}}' for a literal brace)".into()),
+                '[' => stack.push(Vec::new()),
+                ']' => {
+                    if stack.len() == 1 {
+                        return Err("Unmatched ']' in template (write ']]' for a literal bracket)".into());
+                    }
+                    let segment = stack.pop().expect("Checked above");
+                    stack.last_mut().expect("Checked above").push(Part::Optional(segment));
+                }
+                c => push_char(&mut stack, c),
+            }
+        }
+
+        if stack.len() != 1 {
+            return Err("Unclosed '[' in template (write '[[' for a literal bracket)".into());
+        }
+        Ok(Template { parts: stack.pop().expect("Checked above") })
+    }
+}
+
+impl Template {
+    /// Fills the template in with a computed date.
+    pub fn render(&self, value: &ThelemicDateValue) -> String {
+        render_parts(&self.parts, value).0
+    }
+}
+
+/// Renders a sequence of parts, along with whether every placeholder among
+/// them (outside any nested segment) had a value.
+fn render_parts(parts: &[Part], value: &ThelemicDateValue) -> (String, bool) {
+    let mut out = String::new();
+    let mut complete = true;
+    for part in parts {
+        match part {
+            Part::Literal(text) => out.push_str(text),
+            Part::Field(name) => match lookup(value, name) {
+                Some(text) => out.push_str(&text),
+                None => complete = false,
+            },
+            Part::Optional(segment) => {
+                if let (text, true) = render_parts(segment, value) {
+                    out.push_str(&text);
+                }
+            }
+        }
+    }
+    (out, complete)
+}
+
+/// Whether a placeholder name is part of the template language.
+fn is_known(name: &str) -> bool {
+    if let Some(format) = name.strip_prefix("greg:") {
+        return StrftimeItems::new(format).all(|item| !matches!(item, Item::Error));
+    }
+    let (head, attribute) = name.split_once('.').unwrap_or((name, ""));
+    if body_names().any(|body| body == head) {
+        return ["", "glyph", "sign", "sign_glyph", "deg", "angle", "phrase", "long"].contains(&attribute);
+    }
+    matches!(
+        name,
+        "weekday" | "weekday.full" | "weekday.index" | "anno" | "anno.word" | "anno.word_short" | "anno.era" | "anno.abbr" | "anno.ante" | "anno.years" | "anno.cycle_i" | "anno.cycle_ii"
+            | "phase" | "phase.illumination" | "phase.age" | "hour" | "hour.glyph" | "houses" | "lst"
+            | "jd" | "zodiac" | "ayanamsa" | "lat" | "lon" | "date" | "date.long" | "time" | "year" | "month" | "day" | "hour24" | "minute" | "tz"
+    )
+}
+
+/// Lower-case names of every body a template can refer to.
+fn body_names() -> impl Iterator<Item = String> {
+    ["sun", "moon", "asc", "mc"].into_iter()
+        .map(String::from)
+        .chain(Planet::ALL.iter().map(|planet| planet.name().to_lowercase()))
+}
+
+/// The value of a placeholder, or `None` if that component was not computed.
+fn lookup(value: &ThelemicDateValue, name: &str) -> Option<String> {
+    let dt = &value.datetime;
+    if let Some(format) = name.strip_prefix("greg:") {
+        return Some(dt.format(format).to_string());
+    }
+
+    let (head, attribute) = name.split_once('.').unwrap_or((name, ""));
+    let body = match head {
+        "sun" => Some(("☉", value.sun)),
+        "moon" => Some(("☽", value.moon)),
+        "asc" => value.houses.map(|houses| ("Asc", houses.ascendant)),
+        "mc" => value.houses.map(|houses| ("MC", houses.midheaven)),
+        _ => match Planet::ALL.iter().find(|planet| planet.name().eq_ignore_ascii_case(head)) {
+            Some(planet) => Some(value.planets.iter().find(|p| p.planet == *planet).map(|p| (planet.glyph(), p.position))?),
+            None => None,
+        },
+    };
+    if let Some((glyph, position)) = body {
+        return Some(position_field(glyph, head, &position, attribute));
+    }
+
+    let language = value.language;
+    let text = match name {
+        "weekday" => language.day_name(value.weekday).to_string(),
+        "weekday.full" => language.weekday(value.weekday).to_string(),
+        "weekday.index" => value.weekday.to_string(),
+        "anno" => value.anno.numeral(),
+        "anno.word" => language.year(false).to_string(),
+        "anno.word_short" => language.year(true).to_string(),
+        "anno.era" => language.era(value.anno.is_before_era()).to_string(),
+        "anno.abbr" => value.anno.era_abbreviation().to_string(),
+        "anno.ante" if value.anno.is_before_era() => value.anno.era_abbreviation().to_string(),
+        "anno.years" => value.anno.years().to_string(),
+        "anno.cycle_i" => value.anno.cycle_i().to_string(),
+        "anno.cycle_ii" => value.anno.cycle_ii().to_string(),
+        "phase" => value.moon_phase?.phase_name().to_string(),
+        "phase.illumination" => format!("{:.0}", value.moon_phase?.illumination),
+        "phase.age" => format!("{:.1}", value.moon_phase?.age),
+        "hour" => value.planetary_hour?.ruler_name().to_string(),
+        "hour.glyph" if value.style == Style::Ascii => value.planetary_hour?.ruler_name().to_string(),
+        "hour.glyph" => value.planetary_hour?.ruler_glyph().to_string(),
+        "houses" => language.house_system(value.houses?.system).to_string(),
+        "lst" => value.houses?.sidereal_time_hms(),
+        "jd" => format!("{:.5}", value.julian_day),
+        "zodiac" => value.zodiac.name().to_string(),
+        "ayanamsa" => match value.zodiac {
+            Zodiac::Sidereal(ayanamsa) => ayanamsa.name().to_string(),
+            Zodiac::Tropical => return None,
+        },
+        "lat" => format!("{:.4}", value.latitude?),
+        "lon" => format!("{:.4}", value.longitude?),
+        "date" => dt.format("%Y-%m-%d").to_string(),
+        "date.long" => language.date(dt.date_naive()),
+        "time" => dt.format("%H:%M").to_string(),
+        "year" => dt.format("%Y").to_string(),
+        "month" => dt.format("%m").to_string(),
+        "day" => dt.format("%d").to_string(),
+        "hour24" => dt.format("%H").to_string(),
+        "minute" => dt.format("%M").to_string(),
+        "tz" => dt.format("%Z").to_string(),
+        _ => return None,
+    };
+    Some(styled(value.style, text))
+}
+
+/// A body's attribute. In the ASCII style glyphs give way to the body's
+/// name (from `head`, the placeholder's body name) and the sign's name.
+fn position_field(glyph: &str, head: &str, position: &Position, attribute: &str) -> String {
+    let sign_name = position.language.sign(position.sign_index());
+    let text = match (attribute, position.style) {
+        ("glyph", Style::Ascii) => match head {
+            "asc" | "mc" => glyph.to_string(),
+            _ => format!("{}{}", head[..1].to_uppercase(), &head[1..]),
+        },
+        ("glyph", _) => glyph.to_string(),
+        ("sign_glyph", Style::Ascii) => sign_name.to_string(),
+        ("sign_glyph", _) => ThelemicDate::SIGNS[position.sign_index()].1.to_string(),
+        ("deg", _) => position.degree.to_string(),
+        ("angle", _) => position.angle(),
+        ("phrase", _) => position.phrase(),
+        ("long", _) => format!("{:.4}", position.longitude),
+        _ => sign_name.to_string(),
+    };
+    styled(position.style, text)
+}
+
+/// Spells a value in ASCII under the ASCII style.
+fn styled(style: Style, text: String) -> String {
+    if style == Style::Ascii { to_ascii(&text) } else { text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::London;
+
+    fn value(language: Language, style: Style) -> ThelemicDateValue {
+        let dt = London.with_ymd_and_hms(2025, 7, 23, 12, 0, 0).unwrap();
+        ThelemicDate::new()
+            .with_cache(None)
+            .with_language(language)
+            .with_style(style)
+            .at(&dt)
+            .unwrap()
+    }
+
+    fn render(template: &str, value: &ThelemicDateValue) -> String {
+        template.parse::<Template>().unwrap().render(value)
+    }
+
+    #[test]
+    fn parses_literals_fields_and_segments() {
+        let template: Template = "An {anno}[ : {phase}] {{x}} [[y]]".parse().unwrap();
+        assert_eq!(template.parts, vec![
+            Part::Literal("An ".to_string()),
+            Part::Field("anno".to_string()),
+            Part::Optional(vec![Part::Literal(" : ".to_string()), Part::Field("phase".to_string())]),
+            Part::Literal(" {x} [y]".to_string()),
+        ]);
+        assert_eq!("{ sun.sign }".parse::<Template>().unwrap().parts, vec![Part::Field("sun.sign".to_string())]);
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert!("{sun".parse::<Template>().is_err());
+        assert!("sun}".parse::<Template>().is_err());
+        assert!("[{sun}".parse::<Template>().is_err());
+        assert!("{sun}]".parse::<Template>().is_err());
+        assert!("{sun.colour}".parse::<Template>().is_err());
+        assert!("{vulcan}".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn knows_placeholders() {
+        assert!(is_known("sun"));
+        assert!(is_known("moon.sign_glyph"));
+        assert!(is_known("pluto.phrase"));
+        assert!(is_known("asc.long"));
+        assert!(is_known("anno.ante"));
+        assert!(is_known("anno.word_short"));
+        assert!(is_known("greg:%A %e %B"));
+        assert!(!is_known("sun.colour"));
+        assert!(!is_known("weekday.glyph"));
+        assert!(!is_known("greg:%Q"));
+        assert!(!is_known(""));
+    }
+
+    #[test]
+    fn renders_a_date() {
+        let value = value(Language::English, Style::Text);
+        assert_eq!(
+            render("{sun.glyph} {sun.deg} {sun.sign} | dies {weekday} | An {anno}", &value),
+            "☉ 0 Leo | dies Mercurii | An Vxi"
+        );
+        assert_eq!(render("{moon.phrase}, {date.long}", &value), "in 13º Cancer, 23 July 2025");
+        assert_eq!(render("{greg:%A}", &value), "Wednesday");
+    }
+
+    #[test]
+    fn leaves_out_segments_without_values() {
+        let value = value(Language::English, Style::Text);
+        assert_eq!(render("An {anno}[ : {phase}][ {anno.ante}]", &value), "An Vxi");
+        assert_eq!(render("{mercury.sign}|{hour}", &value), "|");
+    }
+
+    #[test]
+    fn follows_language_and_style() {
+        assert_eq!(render("{sun} {weekday.full} {anno.era}", &value(Language::German, Style::Text)), "Löwe Tag des Merkur der Ära des Gesetzes");
+        assert_eq!(render("{sun.glyph} {moon.sign_glyph} {sun.angle}", &value(Language::English, Style::Glyph)), "☉ ♋ 0°");
+        assert_eq!(render("{sun.glyph} {moon.sign_glyph} {sun} {anno.era}", &value(Language::German, Style::Ascii)), "Sun Krebs Loewe der Aera des Gesetzes");
+    }
+}
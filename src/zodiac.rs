@@ -0,0 +1,162 @@
+use astro::{nutation, time};
+use std::str::FromStr;
+
+use crate::{Position, ThelemicDate};
+
+/// The zodiac longitudes are measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Zodiac {
+    /// Measured from the vernal equinox
+    #[default]
+    Tropical,
+    /// Measured from a point fixed against the stars, the given ayanamsa
+    /// behind the vernal equinox
+    Sidereal(Ayanamsa),
+}
+
+/// Ways of fixing the start of the sidereal zodiac.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ayanamsa {
+    Lahiri,
+    FaganBradley,
+    Krishnamurti,
+    Raman,
+    Yukteshwar,
+}
+
+impl Ayanamsa {
+    pub const ALL: [Ayanamsa; 5] = [
+        Ayanamsa::Lahiri, Ayanamsa::FaganBradley, Ayanamsa::Krishnamurti,
+        Ayanamsa::Raman, Ayanamsa::Yukteshwar
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ayanamsa::Lahiri => "Lahiri",
+            Ayanamsa::FaganBradley => "Fagan-Bradley",
+            Ayanamsa::Krishnamurti => "Krishnamurti",
+            Ayanamsa::Raman => "Raman",
+            Ayanamsa::Yukteshwar => "Yukteshwar",
+        }
+    }
+
+    /// Value at J2000.0, in degrees.
+    fn at_j2000(&self) -> f64 {
+        match self {
+            Ayanamsa::Lahiri => 23.857_092,
+            Ayanamsa::FaganBradley => 24.740_300,
+            Ayanamsa::Krishnamurti => 23.760_240,
+            Ayanamsa::Raman => 22.410_791,
+            Ayanamsa::Yukteshwar => 22.478_803,
+        }
+    }
+
+    /// Value at Julian Day `jd`, in degrees, carried from J2000.0 by the
+    /// general precession in longitude.
+    pub fn degrees(&self, jd: f64) -> f64 {
+        let t = time::julian_cent(jd);
+        self.at_j2000() + (5_028.796_195 * t + 1.105_434_8 * t * t) / 3600.0
+    }
+}
+
+impl FromStr for Ayanamsa {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "lahiri" | "chitrapaksha" => Ok(Ayanamsa::Lahiri),
+            "faganbradley" | "fagan" => Ok(Ayanamsa::FaganBradley),
+            "krishnamurti" | "kp" => Ok(Ayanamsa::Krishnamurti),
+            "raman" => Ok(Ayanamsa::Raman),
+            "yukteshwar" | "yukteswar" => Ok(Ayanamsa::Yukteshwar),
+            _ => Err(format!(
+                "Unknown ayanamsa: {} (expected one of {})",
+                s,
+                Ayanamsa::ALL.iter().map(|a| a.name().to_lowercase()).collect::<Vec<_>>().join(", ")
+            ).into()),
+        }
+    }
+}
+
+impl Zodiac {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Zodiac::Tropical => "tropical",
+            Zodiac::Sidereal(_) => "sidereal",
+        }
+    }
+
+    /// Degrees to subtract from a tropical longitude measured from the mean
+    /// equinox at Julian Day `jd`.
+    pub fn offset(&self, jd: f64) -> f64 {
+        match self {
+            Zodiac::Tropical => 0.0,
+            Zodiac::Sidereal(ayanamsa) => ayanamsa.degrees(jd),
+        }
+    }
+}
+
+impl ThelemicDate {
+    /// Degrees to subtract from a tropical longitude at Julian Day `jd` to
+    /// measure it in the selected zodiac. The ayanamsa is counted from the
+    /// mean equinox, while apparent longitudes are counted from the true one,
+    /// so for those the nutation in longitude is taken off as well: sidereal
+    /// positions are then mean positions, fixed against the stars rather than
+    /// nodding with the equinox. Geometric longitudes are already mean.
+    pub(crate) fn zodiac_offset(&self, jd: f64) -> f64 {
+        match self.zodiac {
+            Zodiac::Tropical => 0.0,
+            Zodiac::Sidereal(_) if self.geometric => self.zodiac.offset(jd),
+            Zodiac::Sidereal(_) => {
+                self.zodiac.offset(jd) + nutation::nutation(self.ephemeris_day(jd)).0.to_degrees()
+            }
+        }
+    }
+
+    /// Builds a `Position` from a tropical ecliptic longitude in radians at
+    /// Julian Day `jd`, measured in the selected zodiac.
+    pub fn position_at(&self, longitude: f64, jd: f64) -> Position {
+        self.position(longitude - self.zodiac_offset(jd).to_radians())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::UTC;
+
+    fn jd(y: i32, m: u32, d: u32, h: u32, min: u32) -> f64 {
+        ThelemicDate::julian_day(&UTC.with_ymd_and_hms(y, m, d, h, min, 0).unwrap().naive_utc())
+    }
+
+    #[test]
+    fn matches_published_ayanamsas() {
+        // Mean values for 2025 January 1, 0h UT: Lahiri 24°12', Fagan-Bradley 25°05'
+        let j = jd(2025, 1, 1, 0, 0);
+        assert!((Ayanamsa::Lahiri.degrees(j) - (24.0 + 12.0 / 60.0)).abs() < 1.0 / 60.0);
+        assert!((Ayanamsa::FaganBradley.degrees(j) - (25.0 + 5.0 / 60.0)).abs() < 1.0 / 60.0);
+        assert_eq!(Ayanamsa::Lahiri.degrees(2_451_545.0), 23.857_092);
+        assert_eq!(Zodiac::Tropical.offset(j), 0.0);
+
+        // Precession carries every ayanamsa about 50.3" a year
+        let century = Ayanamsa::FaganBradley.degrees(j + 36_525.0) - Ayanamsa::FaganBradley.degrees(j);
+        assert!((century * 36.0 - 50.3).abs() < 0.1, "{}", century);
+    }
+
+    #[test]
+    fn finds_the_sun_entering_sidereal_aries() {
+        // Mesha Sankranti 2025, April 14 03:30 IST (April 13 22:00 UT)
+        let date_data = ThelemicDate::new().with_cache(None).with_zodiac(Zodiac::Sidereal(Ayanamsa::Lahiri));
+        let j = jd(2025, 4, 13, 22, 0);
+        let longitude = date_data.sun_longitude(j).to_degrees() - date_data.zodiac_offset(j);
+        // The Sun moves a degree a day, so 0.05° is a little over an hour
+        assert!(longitude.abs() < 0.05, "{}", longitude);
+        let position = date_data.position_at(date_data.sun_longitude(j + 1.0), j + 1.0);
+        assert_eq!((position.sign, position.degree), ("Aries", 0));
+    }
+}